@@ -0,0 +1,192 @@
+//! Compares the discriminant tables of two versions of a crate to find the changes that break
+//! compatibility between peers using different versions.
+use std::collections::HashSet;
+use std::fmt;
+
+use super::table::{Table, TableEnum, TableVariant};
+
+/// How a change affects peers that use the other version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Peers on different versions will misread each other.
+    Breaking,
+    /// Peers may still agree, but the change needs a closer look.
+    Warning,
+    /// Peers on different versions still agree on everything they both know.
+    Compatible,
+}
+
+/// A change between two discriminant tables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    /// How the change affects peers.
+    pub severity: Severity,
+    /// What changed.
+    pub message: String,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Breaking => "breaking",
+            Self::Warning => "warning",
+            Self::Compatible => "compatible",
+        })
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+impl Change {
+    /// Creates a change.
+    ///
+    /// * `severity`: How the change affects peers.
+    /// * `message`: What changed.
+    const fn new(severity: Severity, message: String) -> Self {
+        Self { severity, message }
+    }
+}
+
+/// Compares two discriminant tables, returning every change sorted from most to least severe.
+///
+/// Enums are matched by name, along with their file if the name is declared more than once.
+/// Variants are matched by name, except that a variant that disappears while another with the same
+/// discriminant and fields appears is treated as renamed.
+///
+/// * `old`: The table of the previous version.
+/// * `new`: The table of the next version.
+pub fn diff_tables(old: &Table, new: &Table) -> Vec<Change> {
+    let ambiguous = duplicate_names(old).chain(duplicate_names(new)).collect::<HashSet<_>>();
+    let label = |table_enum: &TableEnum| {
+        if ambiguous.contains(table_enum.name.as_str()) {
+            format!("{} ({})", table_enum.name, table_enum.file)
+        } else {
+            table_enum.name.clone()
+        }
+    };
+    let matches = |a: &TableEnum, b: &TableEnum| a.name == b.name && (!ambiguous.contains(a.name.as_str()) || a.file == b.file);
+
+    let mut changes = Vec::new();
+    for old_enum in &old.enums {
+        match new.enums.iter().find(|new_enum| matches(old_enum, new_enum)) {
+            Some(new_enum) => diff_enums(&label(old_enum), old_enum, new_enum, &mut changes),
+            None => changes.push(Change::new(Severity::Breaking, format!("`{}` was removed", label(old_enum)))),
+        }
+    }
+    for new_enum in new.enums.iter().filter(|new_enum| !old.enums.iter().any(|old_enum| matches(old_enum, new_enum))) {
+        changes.push(Change::new(Severity::Compatible, format!("`{}` was added", label(new_enum))));
+    }
+    changes.sort_by_key(|change| change.severity);
+    changes
+}
+
+/// Returns the names of the enums that are declared more than once in a table.
+///
+/// * `table`: The table.
+fn duplicate_names(table: &Table) -> impl Iterator<Item = &str> {
+    let mut seen = HashSet::new();
+    table.enums.iter().map(|table_enum| table_enum.name.as_str()).filter(move |name| !seen.insert(*name))
+}
+
+/// Compares two versions of an enum.
+///
+/// * `label`: The name of the enum to use in messages.
+/// * `old`: The previous version of the enum.
+/// * `new`: The next version of the enum.
+/// * `changes`: The changes found so far.
+fn diff_enums(label: &str, old: &TableEnum, new: &TableEnum, changes: &mut Vec<Change>) {
+    if old.repr != new.repr {
+        changes.push(Change::new(Severity::Breaking, format!("`{label}` changed its repr from `{}` to `{}`", old.repr, new.repr)));
+    }
+
+    let removed = old.variants.iter().filter(|variant| !new.variants.iter().any(|new_variant| new_variant.name == variant.name)).collect::<Vec<_>>();
+    let added = new.variants.iter().filter(|variant| !old.variants.iter().any(|old_variant| old_variant.name == variant.name)).collect::<Vec<_>>();
+    let renamed = |variant: &TableVariant| {
+        added
+            .iter()
+            .find(|new_variant| !variant.retired && new_variant.discriminant.is_some() && new_variant.discriminant == variant.discriminant && new_variant.fields == variant.fields)
+    };
+
+    for old_variant in &old.variants {
+        if let Some(new_variant) = new.variants.iter().find(|new_variant| new_variant.name == old_variant.name) {
+            diff_variants(label, old_variant, new_variant, changes);
+        } else if let Some(new_variant) = renamed(old_variant) {
+            changes.push(Change::new(
+                Severity::Warning,
+                format!("`{label}::{}` was renamed to `{}`, which breaks peers that use its name", old_variant.name, new_variant.name),
+            ));
+        } else if old_variant.retired {
+            changes.push(Change::new(
+                Severity::Warning,
+                format!("`{label}::{}` was retired and is now removed, so its discriminant `{}` is no longer reserved", old_variant.name, discriminant(old_variant)),
+            ));
+        } else {
+            changes.push(Change::new(
+                Severity::Breaking,
+                format!("`{label}::{}` was removed. Keep it and mark it `#[discriminant(retired)]` so that its discriminant is not reused", old_variant.name),
+            ));
+        }
+    }
+
+    for &new_variant in &added {
+        if removed.iter().any(|old_variant| renamed(old_variant).is_some_and(|renamed| renamed.name == new_variant.name)) {
+            continue;
+        }
+        let reused = new_variant.discriminant.as_ref().and_then(|value| old.variants.iter().find(|old_variant| old_variant.discriminant.as_ref() == Some(value)));
+        let change = match (reused, &new_variant.discriminant) {
+            (Some(old_variant), Some(value)) => Change::new(Severity::Breaking, format!("`{label}::{}` reuses the discriminant `{value}` of `{label}::{}`", new_variant.name, old_variant.name)),
+            (_, Some(value)) => Change::new(Severity::Compatible, format!("`{label}::{}` was added with the discriminant `{value}`", new_variant.name)),
+            (_, None) => Change::new(
+                Severity::Warning,
+                format!("`{label}::{}` was added with the discriminant `{}`, which cannot be checked without compiling", new_variant.name, new_variant.expr),
+            ),
+        };
+        changes.push(change);
+    }
+}
+
+/// Compares two versions of a variant with the same name.
+///
+/// * `label`: The name of the enum to use in messages.
+/// * `old`: The previous version of the variant.
+/// * `new`: The next version of the variant.
+/// * `changes`: The changes found so far.
+fn diff_variants(label: &str, old: &TableVariant, new: &TableVariant, changes: &mut Vec<Change>) {
+    let name = &old.name;
+    match (&old.discriminant, &new.discriminant) {
+        (Some(old_value), Some(new_value)) if old_value != new_value => {
+            changes.push(Change::new(Severity::Breaking, format!("`{label}::{name}` was renumbered from `{old_value}` to `{new_value}`")));
+        }
+        (None, _) | (_, None) if old.expr != new.expr => changes.push(Change::new(
+            Severity::Warning,
+            format!("`{label}::{name}` changed its discriminant from `{}` to `{}`, which cannot be compared without compiling", old.expr, new.expr),
+        )),
+        _ => {}
+    }
+    if old.fields != new.fields {
+        changes.push(Change::new(Severity::Breaking, format!("`{label}::{name}` changed its fields from {} to {}", fields(old), fields(new))));
+    }
+    match (old.retired, new.retired) {
+        (false, true) => changes.push(Change::new(Severity::Warning, format!("`{label}::{name}` was retired, so peers that still send it will be rejected"))),
+        (true, false) => changes.push(Change::new(Severity::Warning, format!("`{label}::{name}` is no longer retired, so older peers will reject it"))),
+        _ => {}
+    }
+}
+
+/// Writes the discriminant of a variant for messages, falling back to its expression.
+///
+/// * `variant`: The variant.
+fn discriminant(variant: &TableVariant) -> &str {
+    variant.discriminant.as_deref().unwrap_or(&variant.expr)
+}
+
+/// Writes the fields of a variant for messages, e.g. `` `(u32)` `` or `no fields`.
+///
+/// * `variant`: The variant.
+fn fields(variant: &TableVariant) -> String {
+    if variant.fields.is_empty() { "no fields".to_string() } else { format!("`{}`", variant.fields) }
+}
@@ -0,0 +1,158 @@
+//! # Discrimin-Ant CLI
+//!
+//! The `discrimin-ant` command line tool reads the discriminants of `discriminant` enums straight
+//! from Rust sources, without compiling the crate. Discriminants are resolved the same way as the
+//! macros resolve them, so the numbering can be reviewed by anyone, including teams that do not
+//! use Rust. It can also compare the tables of two versions of a crate to catch changes that break
+//! compatibility before they are released.
+mod diff;
+mod scan;
+mod table;
+#[cfg(test)]
+mod tests;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use diff::Severity;
+use table::{Format, Table};
+
+/// The usage shown by `--help` and after invalid arguments.
+const USAGE: &str = "\
+Usage: discrimin-ant scan [--format text|json|csv] [PATH]...
+       discrimin-ant diff OLD NEW
+
+scan: Prints the discriminant of every variant of the `discriminant` enums in the Rust sources at
+      each PATH, which may be a file or a directory. Defaults to the current directory.
+diff: Compares the discriminant tables of two versions of a crate, each given as a path to its
+      sources or a table written by `scan --format json`. Exits with `1` if any change breaks
+      compatibility.";
+
+/// A command given on the command line.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    /// Print the discriminant table of the sources at the paths.
+    Scan {
+        /// The format to print the table in.
+        format: Format,
+        /// The files and directories to scan.
+        paths: Vec<PathBuf>,
+    },
+    /// Print the changes between the discriminant tables of two versions of a crate.
+    Diff {
+        /// The sources or table of the previous version.
+        old: PathBuf,
+        /// The sources or table of the next version.
+        new: PathBuf,
+    },
+    /// Print the usage.
+    Help,
+}
+
+impl Command {
+    /// Parses the command from the arguments, not including the name of the program.
+    ///
+    /// * `args`: The arguments.
+    ///
+    /// # Errors
+    /// Fails if the command or one of its options is unknown or missing a value.
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let Some((command, args)) = args.split_first() else {
+            return Err(format!("Missing a command\n\n{USAGE}"));
+        };
+        match command.as_str() {
+            "scan" => {
+                let mut format = Format::Text;
+                let mut paths = Vec::new();
+                let mut args = args.iter();
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--format" => format = args.next().ok_or("`--format` needs a value")?.parse()?,
+                        option if option.starts_with('-') => return Err(format!("Unknown option `{option}`\n\n{USAGE}")),
+                        path => paths.push(PathBuf::from(path)),
+                    }
+                }
+                if paths.is_empty() {
+                    paths.push(PathBuf::from("."));
+                }
+                Ok(Self::Scan { format, paths })
+            }
+            "diff" => match args {
+                [old, new] => Ok(Self::Diff {
+                    old: PathBuf::from(old),
+                    new: PathBuf::from(new),
+                }),
+                _ => Err(format!("`diff` needs the previous and next version\n\n{USAGE}")),
+            },
+            "help" | "-h" | "--help" => Ok(Self::Help),
+            command => Err(format!("Unknown command `{command}`\n\n{USAGE}")),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    run(&args).unwrap_or_else(|error| {
+        eprintln!("error: {error}");
+        ExitCode::from(2)
+    })
+}
+
+/// Runs the command given by the arguments.
+///
+/// Problems with individual enums are reported on stderr without stopping the scan, but make the
+/// command fail once it has finished.
+///
+/// * `args`: The arguments, not including the name of the program.
+///
+/// # Errors
+/// Fails if the arguments are invalid or the sources cannot be read.
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    match Command::parse(args)? {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Scan { format, paths } => {
+            let scan = scan::scan_paths(&paths)?;
+            for warning in &scan.warnings {
+                eprintln!("warning: {warning}");
+            }
+            for error in &scan.errors {
+                eprintln!("error: {error}");
+            }
+            print!("{}", format.render(&scan.table)?);
+            Ok(if scan.errors.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+        }
+        Command::Diff { old, new } => {
+            let changes = diff::diff_tables(&load_table(&old)?, &load_table(&new)?);
+            for change in &changes {
+                println!("{change}");
+            }
+            let count = |severity| changes.iter().filter(|change| change.severity == severity).count();
+            let breaking = count(Severity::Breaking);
+            println!("{breaking} breaking, {} warnings, {} compatible", count(Severity::Warning), count(Severity::Compatible));
+            Ok(if breaking == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+        }
+    }
+}
+
+/// Loads the discriminant table of a version of a crate, either by reading a table written by
+/// `scan --format json` or by scanning its sources.
+///
+/// * `path`: The `.json` table, or the file or directory of the sources.
+///
+/// # Errors
+/// Fails if the table cannot be read, or if an enum in the sources cannot be resolved, since a
+/// missing enum would hide its changes.
+fn load_table(path: &Path) -> Result<Table, String> {
+    if path.is_file() && path.extension().is_some_and(|extension| extension == "json") {
+        let json = std::fs::read_to_string(path).map_err(|error| format!("Could not read `{}`: {error}", path.display()))?;
+        return serde_json::from_str(&json).map_err(|error| format!("`{}` is not a discriminant table: {error}", path.display()));
+    }
+    let scan = scan::scan_paths(&[path.to_path_buf()])?;
+    if !scan.errors.is_empty() {
+        return Err(format!("Could not resolve every enum in `{}`:\n{}", path.display(), scan.errors.join("\n")));
+    }
+    Ok(scan.table)
+}
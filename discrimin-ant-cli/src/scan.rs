@@ -0,0 +1,180 @@
+//! Finds the enums that use the `discriminant` attribute or the `Discriminant` derive in Rust
+//! sources and resolves their discriminants.
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use discrimin_ant_codegen::DiscriminantEnum;
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::visit::Visit;
+use syn::{Attribute, DeriveInput, ItemEnum, Meta, Token};
+
+use super::table::{Table, TableEnum};
+
+/// The enums found in the scanned sources, along with any problems found on the way.
+#[derive(Debug, Default)]
+pub struct Scan {
+    /// The discriminant table of every enum that could be resolved.
+    pub table: Table,
+    /// Discriminants that cannot be resolved without compiling the crate.
+    pub warnings: Vec<String>,
+    /// Sources that do not parse and enums that the macros would reject.
+    pub errors: Vec<String>,
+}
+
+/// Scans every Rust source in the paths. Directories are searched recursively, skipping hidden
+/// directories and `target`.
+///
+/// Sources found in a directory are named relative to it, so that two checkouts of a crate produce
+/// the same table.
+///
+/// * `paths`: The files and directories to scan.
+///
+/// # Errors
+/// Fails if a path cannot be read.
+pub fn scan_paths(paths: &[PathBuf]) -> Result<Scan, String> {
+    let mut scan = Scan::default();
+    for path in paths {
+        let mut files = Vec::new();
+        collect_sources(path, &mut files)?;
+        files.sort();
+        for file in files {
+            let source = std::fs::read_to_string(&file).map_err(|error| format!("Could not read `{}`: {error}", file.display()))?;
+            let name = file.strip_prefix(path).ok().filter(|name| !name.as_os_str().is_empty()).unwrap_or(&file);
+            scan_source(&name.display().to_string(), &source, &mut scan);
+        }
+    }
+    Ok(scan)
+}
+
+/// Collects the Rust sources in a path. A file is always collected, even without the `.rs`
+/// extension, since it was asked for by name.
+///
+/// * `path`: The file or directory.
+/// * `files`: The sources found so far.
+///
+/// # Errors
+/// Fails if the path or one of its directories cannot be read.
+fn collect_sources(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let entries = std::fs::read_dir(path).map_err(|error| format!("Could not read `{}`: {error}", path.display()))?;
+    for entry in entries {
+        let path = entry.map_err(|error| format!("Could not read `{}`: {error}", path.display()))?.path();
+        let skipped = path.file_name().and_then(OsStr::to_str).is_some_and(|name| name.starts_with('.') || name == "target");
+        if skipped {
+            continue;
+        }
+        if path.is_dir() {
+            collect_sources(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Scans a single Rust source, adding its enums and problems to `scan`.
+///
+/// * `file`: The path of the source, used in the table and in messages.
+/// * `source`: The contents of the source.
+/// * `scan`: The scan to add to.
+pub fn scan_source(file: &str, source: &str, scan: &mut Scan) {
+    match syn::parse_file(source) {
+        Ok(syntax) => EnumVisitor { file, scan }.visit_file(&syntax),
+        Err(error) => scan.errors.extend(located(file, &error, Span::call_site())),
+    }
+}
+
+/// Visits every enum in a source, including those in nested modules and functions.
+struct EnumVisitor<'a> {
+    /// The path of the source.
+    file: &'a str,
+    /// The scan to add to.
+    scan: &'a mut Scan,
+}
+
+impl<'ast> Visit<'ast> for EnumVisitor<'_> {
+    fn visit_item_enum(&mut self, item: &'ast ItemEnum) {
+        match resolve_enum(item) {
+            Some(Ok(resolved)) => {
+                for variant in resolved.variants.iter().filter(|variant| variant.value.is_none()) {
+                    self.scan.warnings.push(format!(
+                        "{}: The discriminant of `{}::{}` is `{}`, which cannot be resolved without compiling the crate",
+                        location(self.file, variant.ident().span()),
+                        resolved.ident.unraw(),
+                        variant.ident().unraw(),
+                        variant.expr.to_token_stream(),
+                    ));
+                }
+                self.scan.table.enums.push(TableEnum::new(self.file, &resolved));
+            }
+            Some(Err(error)) => self.scan.errors.extend(located(self.file, &error, item.ident.span())),
+            None => {}
+        }
+    }
+}
+
+/// Resolves the discriminants of an enum that uses the `discriminant` attribute or the
+/// `Discriminant` derive, or returns `None` if it uses neither.
+///
+/// * `item`: The enum.
+fn resolve_enum(item: &ItemEnum) -> Option<syn::Result<DiscriminantEnum>> {
+    let mut input = DeriveInput::from(item.clone());
+    if input.attrs.iter().any(derives_discriminant) {
+        return Some(DiscriminantEnum::from_derive(&input));
+    }
+    let position = input.attrs.iter().position(|attr| attr.path().segments.last().is_some_and(|segment| segment.ident == "discriminant"))?;
+    let attr = input.attrs.remove(position);
+    let args = match &attr.meta {
+        Meta::Path(_) => TokenStream::new(),
+        meta => match meta.require_list() {
+            Ok(list) => list.tokens.clone(),
+            Err(error) => return Some(Err(error)),
+        },
+    };
+    Some(DiscriminantEnum::from_attribute(args, &input))
+}
+
+/// Checks whether an attribute is a `derive` that includes `Discriminant`.
+///
+/// * `attr`: The attribute.
+fn derives_discriminant(attr: &Attribute) -> bool {
+    attr.path().is_ident("derive")
+        && attr
+            .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+            .is_ok_and(|derives| derives.iter().any(|derive| derive.segments.last().is_some_and(|segment| segment.ident == "Discriminant")))
+}
+
+/// Writes each message of an error along with where it occurred.
+///
+/// * `file`: The path of the source.
+/// * `error`: The error.
+/// * `fallback`: Where to point messages that do not have a location of their own, such as those
+///   about the options of an enum.
+fn located(file: &str, error: &syn::Error, fallback: Span) -> Vec<String> {
+    error
+        .clone()
+        .into_iter()
+        .map(|error| {
+            let span = if error.span().start().line == 0 { fallback } else { error.span() };
+            format!("{}: {error}", location(file, span))
+        })
+        .collect()
+}
+
+/// Writes a location in a source as `file:line:column`.
+///
+/// * `file`: The path of the source.
+/// * `span`: The location in the source.
+fn location(file: &str, span: Span) -> String {
+    let start = span.start();
+    if start.line == 0 {
+        return file.to_string();
+    }
+    format!("{file}:{}:{}", start.line, start.column.saturating_add(1))
+}
@@ -0,0 +1,187 @@
+//! The discriminant table of the scanned enums and the formats it can be written in.
+use std::fmt::Write;
+use std::str::FromStr;
+
+use discrimin_ant_codegen::{DiscriminantEnum, DiscriminantVariant};
+use quote::ToTokens;
+use serde::{Deserialize, Serialize};
+use syn::ext::IdentExt;
+
+/// The discriminant of every variant of the scanned enums.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table {
+    /// The enums, in the order they were found.
+    pub enums: Vec<TableEnum>,
+}
+
+/// An enum in a [`Table`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableEnum {
+    /// The name of the enum.
+    pub name: String,
+    /// The source that declares the enum.
+    pub file: String,
+    /// The repr for the enum, e.g. `u8`.
+    pub repr: String,
+    /// Every variant of the enum in declaration order, including retired ones.
+    pub variants: Vec<TableVariant>,
+}
+
+/// A variant of a [`TableEnum`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableVariant {
+    /// The name of the variant.
+    pub name: String,
+    /// The discriminant in decimal, or `None` if it cannot be resolved without compiling the crate.
+    /// It is written as a string so that 64 and 128-bit discriminants survive JSON parsers that
+    /// read every number as a double.
+    pub discriminant: Option<String>,
+    /// The expression for the discriminant, e.g. `BASE + 1`.
+    pub expr: String,
+    /// The fields of the variant as written in Rust, e.g. `(u32, String)` or `{ x: i32 }`.
+    pub fields: String,
+    /// Whether the variant is marked `#[discriminant(retired)]`.
+    pub retired: bool,
+}
+
+/// A format to write a [`Table`] in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Aligned columns for people to read.
+    Text,
+    /// JSON, which can be read back as a [`Table`].
+    Json,
+    /// CSV with a header row.
+    Csv,
+}
+
+impl TableEnum {
+    /// Creates the table entry for a resolved enum.
+    ///
+    /// * `file`: The source that declares the enum.
+    /// * `resolved`: The enum.
+    pub fn new(file: &str, resolved: &DiscriminantEnum) -> Self {
+        Self {
+            name: resolved.ident.unraw().to_string(),
+            file: file.to_string(),
+            repr: resolved.repr.to_string(),
+            variants: resolved.variants.iter().map(TableVariant::new).collect(),
+        }
+    }
+}
+
+impl TableVariant {
+    /// Creates the table entry for a resolved variant.
+    ///
+    /// * `variant`: The variant.
+    fn new(variant: &DiscriminantVariant) -> Self {
+        Self {
+            name: variant.ident().unraw().to_string(),
+            discriminant: variant.value.map(|value| value.to_string()),
+            expr: variant.expr.to_token_stream().to_string(),
+            fields: variant.shape().to_rust().trim_start().to_string(),
+            retired: variant.retired,
+        }
+    }
+
+    /// Describes anything notable about the variant, e.g. `retired`.
+    fn note(&self) -> String {
+        let notes = [self.retired.then_some("retired"), self.discriminant.is_none().then_some("unresolved")];
+        notes.into_iter().flatten().collect::<Vec<_>>().join(", ")
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Unknown format `{s}`. Use `text`, `json` or `csv`")),
+        }
+    }
+}
+
+impl Format {
+    /// Writes a table in this format.
+    ///
+    /// * `table`: The table.
+    ///
+    /// # Errors
+    /// Fails if the table cannot be serialized.
+    pub fn render(self, table: &Table) -> Result<String, String> {
+        match self {
+            Self::Text => Ok(render_text(table)),
+            Self::Json => serde_json::to_string_pretty(table).map(|json| json + "\n").map_err(|error| format!("Could not write the table as JSON: {error}")),
+            Self::Csv => Ok(render_csv(table)),
+        }
+    }
+}
+
+/// Writes a table as aligned columns. Unresolved discriminants are shown as their expression.
+///
+/// * `table`: The table.
+fn render_text(table: &Table) -> String {
+    let header = ["Enum", "Repr", "Variant", "Discriminant", "Fields", "Note"].map(String::from);
+    let rows = table
+        .enums
+        .iter()
+        .flat_map(|table_enum| {
+            table_enum.variants.iter().map(|variant| {
+                [
+                    table_enum.name.clone(),
+                    table_enum.repr.clone(),
+                    variant.name.clone(),
+                    variant.discriminant.clone().unwrap_or_else(|| variant.expr.clone()),
+                    variant.fields.clone(),
+                    variant.note(),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut widths = [0; 6];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut text = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = row.iter().zip(widths).map(|(cell, width)| format!("{cell:width$}")).collect::<Vec<_>>().join("  ");
+        let _ = writeln!(text, "{}", line.trim_end());
+    }
+    text
+}
+
+/// Writes a table as CSV with a header row. Unresolved discriminants are left empty.
+///
+/// * `table`: The table.
+fn render_csv(table: &Table) -> String {
+    let mut csv = String::from("file,enum,repr,variant,discriminant,expr,fields,retired\n");
+    for table_enum in &table.enums {
+        for variant in &table_enum.variants {
+            let row = [
+                table_enum.file.as_str(),
+                &table_enum.name,
+                &table_enum.repr,
+                &variant.name,
+                variant.discriminant.as_deref().unwrap_or_default(),
+                &variant.expr,
+                &variant.fields,
+                if variant.retired { "true" } else { "false" },
+            ];
+            let _ = writeln!(csv, "{}", row.map(csv_field).join(","));
+        }
+    }
+    csv
+}
+
+/// Quotes a CSV field if it contains a comma, quote or line break.
+///
+/// * `field`: The field.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) { format!("\"{}\"", field.replace('"', "\"\"")) } else { field.to_string() }
+}
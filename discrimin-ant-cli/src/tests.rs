@@ -0,0 +1,297 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+use scan::{Scan, scan_source};
+use table::{Table, TableEnum, TableVariant};
+
+/// The source scanned by most of the tests.
+const SOURCE: &str = r"
+use discrimin_ant::{Discriminant, discriminant};
+
+const BASE: u8 = 10;
+
+#[discriminant(u8, serde)]
+pub enum Message {
+    Ping = 1,
+    Data(u32, String),
+    #[discriminant(retired)]
+    Old,
+    Move { x: i32, y: i32 } = 5,
+}
+
+pub enum Plain {
+    A,
+}
+
+mod nested {
+    #[derive(Clone, discrimin_ant::Discriminant)]
+    #[repr(i16)]
+    enum Dynamic {
+        Low = -1,
+        High = super::BASE as i16,
+        Higher,
+    }
+}
+";
+
+/// Scans a source, returning everything that was found.
+///
+/// * `source`: The source.
+fn scan(source: &str) -> Scan {
+    let mut scan = Scan::default();
+    scan_source("src/lib.rs", source, &mut scan);
+    scan
+}
+
+/// Creates a variant for an expected table.
+///
+/// * `name`: The name of the variant.
+/// * `discriminant`: The discriminant, if it is resolved.
+/// * `expr`: The expression for the discriminant.
+/// * `fields`: The fields of the variant.
+fn variant(name: &str, discriminant: Option<&str>, expr: &str, fields: &str) -> TableVariant {
+    TableVariant {
+        name: name.to_string(),
+        discriminant: discriminant.map(String::from),
+        expr: expr.to_string(),
+        fields: fields.to_string(),
+        retired: false,
+    }
+}
+
+#[test]
+fn scans_discriminant_enums() {
+    let scan = scan(SOURCE);
+    assert_eq!(scan.errors, Vec::<String>::new());
+    let expected = Table {
+        enums: vec![
+            TableEnum {
+                name: "Message".to_string(),
+                file: "src/lib.rs".to_string(),
+                repr: "u8".to_string(),
+                variants: vec![
+                    variant("Ping", Some("1"), "1", ""),
+                    variant("Data", Some("2"), "1 + 1u8", "(u32, String)"),
+                    TableVariant {
+                        retired: true,
+                        ..variant("Old", Some("3"), "1 + 2u8", "")
+                    },
+                    variant("Move", Some("5"), "5", "{ x: i32, y: i32 }"),
+                ],
+            },
+            TableEnum {
+                name: "Dynamic".to_string(),
+                file: "src/lib.rs".to_string(),
+                repr: "i16".to_string(),
+                variants: vec![
+                    variant("Low", Some("-1"), "- 1", ""),
+                    variant("High", None, "super :: BASE as i16", ""),
+                    variant("Higher", None, "super :: BASE as i16 + 1i16", ""),
+                ],
+            },
+        ],
+    };
+    assert_eq!(scan.table, expected);
+}
+
+#[test]
+fn scans_raw_identifiers() {
+    let scan = scan("#[discriminant(u8)]\nenum r#Keyword {\n    r#async,\n    r#yield = super::BASE,\n}\n");
+    let expected = Table {
+        enums: vec![TableEnum {
+            name: "Keyword".to_string(),
+            file: "src/lib.rs".to_string(),
+            repr: "u8".to_string(),
+            variants: vec![variant("async", Some("0"), "0u8", ""), variant("yield", None, "super :: BASE", "")],
+        }],
+    };
+    assert_eq!(scan.table, expected);
+    assert_eq!(scan.warnings, ["src/lib.rs:4:5: The discriminant of `Keyword::yield` is `super :: BASE`, which cannot be resolved without compiling the crate"]);
+}
+
+#[test]
+fn flags_unresolved_discriminants() {
+    let scan = scan(SOURCE);
+    assert_eq!(
+        scan.warnings,
+        [
+            "src/lib.rs:24:9: The discriminant of `Dynamic::High` is `super :: BASE as i16`, which cannot be resolved without compiling the crate",
+            "src/lib.rs:25:9: The discriminant of `Dynamic::Higher` is `super :: BASE as i16 + 1i16`, which cannot be resolved without compiling the crate",
+        ]
+    );
+}
+
+#[test]
+fn reports_invalid_enums() {
+    let scanned = scan("#[discriminant(u8)]\nenum TooBig {\n    A = 256,\n}\n\n#[discriminant(u8, explicit)]\nenum Implicit {\n    A = 1,\n    B,\n}\n");
+    assert_eq!(
+        scanned.errors,
+        [
+            "src/lib.rs:3:9: The discriminant `256` does not fit in `u8`",
+            "src/lib.rs:9:5: `B` must have an explicit discriminant. Add `= 2` to keep its current value",
+        ]
+    );
+    assert_eq!(scanned.table, Table::default());
+
+    let scanned = scan("#[discriminant(u8, reserved(1))]\nenum A {\n    X = 1,\n    Y = 1,\n    Z(u8),\n}\n\n#[discriminant(u8, reserved(1))]\nenum B {\n    X = 1,\n    #[discriminant(retired)]\n    Y,\n    Z = 2,\n}\n");
+    assert_eq!(
+        scanned.errors,
+        [
+            "src/lib.rs:4:5: `Y` has the discriminant `1`, which is already used by `X`",
+            "src/lib.rs:10:5: `X` has the discriminant `1`, which is reserved",
+            "src/lib.rs:13:5: `Z` has the discriminant `2`, which was retired by `Y`",
+        ]
+    );
+    assert_eq!(scanned.table, Table::default());
+
+    let scanned = scan("enum Broken {");
+    assert_eq!(scanned.errors.len(), 1);
+    assert!(scanned.errors[0].starts_with("src/lib.rs:"));
+}
+
+#[test]
+fn renders_text() {
+    let text = Format::Text.render(&scan(SOURCE).table);
+    let expected = "\
+Enum     Repr  Variant  Discriminant                 Fields              Note
+Message  u8    Ping     1
+Message  u8    Data     2                            (u32, String)
+Message  u8    Old      3                                                retired
+Message  u8    Move     5                            { x: i32, y: i32 }
+Dynamic  i16   Low      -1
+Dynamic  i16   High     super :: BASE as i16                             unresolved
+Dynamic  i16   Higher   super :: BASE as i16 + 1i16                      unresolved
+";
+    assert_eq!(text.as_deref(), Ok(expected));
+}
+
+#[test]
+fn renders_csv() {
+    let csv = Format::Csv.render(&scan(SOURCE).table);
+    let expected = "\
+file,enum,repr,variant,discriminant,expr,fields,retired
+src/lib.rs,Message,u8,Ping,1,1,,false
+src/lib.rs,Message,u8,Data,2,1 + 1u8,\"(u32, String)\",false
+src/lib.rs,Message,u8,Old,3,1 + 2u8,,true
+src/lib.rs,Message,u8,Move,5,5,\"{ x: i32, y: i32 }\",false
+src/lib.rs,Dynamic,i16,Low,-1,- 1,,false
+src/lib.rs,Dynamic,i16,High,,super :: BASE as i16,,false
+src/lib.rs,Dynamic,i16,Higher,,super :: BASE as i16 + 1i16,,false
+";
+    assert_eq!(csv.as_deref(), Ok(expected));
+}
+
+#[test]
+fn renders_json() {
+    let table = scan(SOURCE).table;
+    #[allow(clippy::unwrap_used)]
+    let json = Format::Json.render(&table).unwrap();
+    assert!(json.contains(r#""discriminant": "5""#));
+    assert!(json.contains(r#""discriminant": null"#));
+    #[allow(clippy::unwrap_used)]
+    let parsed = serde_json::from_str::<Table>(&json).unwrap();
+    assert_eq!(parsed, table);
+}
+
+#[test]
+fn parses_commands() {
+    let args = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(
+        Command::parse(&args(&["scan"])),
+        Ok(Command::Scan {
+            format: Format::Text,
+            paths: vec![PathBuf::from(".")]
+        })
+    );
+    assert_eq!(
+        Command::parse(&args(&["scan", "--format", "csv", "a", "b.rs"])),
+        Ok(Command::Scan {
+            format: Format::Csv,
+            paths: vec![PathBuf::from("a"), PathBuf::from("b.rs")]
+        })
+    );
+    assert_eq!(
+        Command::parse(&args(&["diff", "old", "new.json"])),
+        Ok(Command::Diff {
+            old: PathBuf::from("old"),
+            new: PathBuf::from("new.json")
+        })
+    );
+    assert!(Command::parse(&args(&["diff", "old"])).is_err_and(|error| error.starts_with("`diff` needs the previous and next version")));
+    assert_eq!(Command::parse(&args(&["--help"])), Ok(Command::Help));
+    assert_eq!(Command::parse(&args(&["scan", "--format", "xml"])), Err("Unknown format `xml`. Use `text`, `json` or `csv`".to_string()));
+    assert_eq!(Command::parse(&args(&["scan", "--format"])), Err("`--format` needs a value".to_string()));
+    assert!(Command::parse(&args(&["dump"])).is_err_and(|error| error.starts_with("Unknown command `dump`")));
+}
+
+/// Compares the tables of two sources, returning each change as it is printed.
+///
+/// * `old`: The source of the previous version.
+/// * `new`: The source of the next version.
+fn diff(old: &str, new: &str) -> Vec<String> {
+    diff::diff_tables(&scan(old).table, &scan(new).table).iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn diffs_breaking_changes() {
+    let old = "
+        #[discriminant(u8)]
+        enum Message { Ping = 1, Data(u32), Move { x: i32 } = 5, Stop, Old }
+        #[discriminant(u8)]
+        enum Gone { A }
+    ";
+    let new = "
+        #[discriminant(u16)]
+        enum Message { Ping = 1, Data(u64) = 3, Move { x: i32 } = 5, #[discriminant(retired)] Stop, Reset = 2, New = 10 }
+        #[discriminant(u8)]
+        enum Added { A }
+    ";
+    assert_eq!(
+        diff(old, new),
+        [
+            "breaking: `Message` changed its repr from `u8` to `u16`",
+            "breaking: `Message::Data` was renumbered from `2` to `3`",
+            "breaking: `Message::Data` changed its fields from `(u32)` to `(u64)`",
+            "breaking: `Message::Old` was removed. Keep it and mark it `#[discriminant(retired)]` so that its discriminant is not reused",
+            "breaking: `Message::Reset` reuses the discriminant `2` of `Message::Data`",
+            "breaking: `Gone` was removed",
+            "warning: `Message::Stop` was retired, so peers that still send it will be rejected",
+            "compatible: `Message::New` was added with the discriminant `10`",
+            "compatible: `Added` was added",
+        ]
+    );
+}
+
+#[test]
+fn diffs_compatible_changes() {
+    let old = "#[discriminant(u8)]\nenum Event { A = 1, B, #[discriminant(retired)] C, D = BASE }";
+    let new = "#[discriminant(u8)]\nenum Event { A = 1, Bee = 2, E = 4, D = BASE + 1 }";
+    assert_eq!(
+        diff(old, new),
+        [
+            "warning: `Event::B` was renamed to `Bee`, which breaks peers that use its name",
+            "warning: `Event::C` was retired and is now removed, so its discriminant `3` is no longer reserved",
+            "warning: `Event::D` changed its discriminant from `BASE` to `BASE + 1`, which cannot be compared without compiling",
+            "compatible: `Event::E` was added with the discriminant `4`",
+        ]
+    );
+    assert_eq!(diff(old, old), Vec::<String>::new());
+}
+
+#[test]
+fn diffs_enums_with_the_same_name_by_file() {
+    let message = |discriminant: u8| TableEnum {
+        name: "Message".to_string(),
+        file: "src/a.rs".to_string(),
+        repr: "u8".to_string(),
+        variants: vec![variant("Ping", Some(&discriminant.to_string()), &discriminant.to_string(), "")],
+    };
+    let old = Table {
+        enums: vec![message(1), TableEnum { file: "src/b.rs".to_string(), ..message(1) }],
+    };
+    let new = Table {
+        enums: vec![message(1), TableEnum { file: "src/b.rs".to_string(), ..message(2) }],
+    };
+    let changes = diff::diff_tables(&old, &new).iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(changes, ["breaking: `Message (src/b.rs)::Ping` was renumbered from `1` to `2`"]);
+}
@@ -0,0 +1,813 @@
+//! Contains the implementation for the discriminant proc macro.
+mod analysis;
+mod case;
+mod code;
+mod codec;
+mod export;
+mod lock;
+mod options;
+mod repr;
+mod reserved;
+mod schema;
+mod schemars;
+mod serde;
+#[cfg(test)]
+mod tests;
+mod value;
+
+use std::collections::HashMap;
+
+use num_traits::PrimInt;
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Expr, Ident, Variant, parse_quote};
+
+pub use analysis::{DiscriminantEnum, DiscriminantVariant, FieldShape};
+pub use code::generate_code_newtype;
+pub use codec::generate_codec_impls;
+pub use schema::schema_hash;
+pub use schemars::generate_schemars_impl;
+pub use serde::generate_serde_impls;
+pub use value::{DiscriminantValue, check_duplicate_discriminants};
+
+use case::Case;
+use options::{DiscriminantOptions, FieldlessOptions, VariantOptions};
+use repr::Repr;
+
+/// The catch-all variant of the fieldless enum, which holds any discriminant that does not belong to
+/// a variant.
+pub struct OtherVariant {
+    /// The name of the variant in the fieldless enum.
+    pub ident: Ident,
+    /// The name of the variant after any renames.
+    pub name: String,
+}
+
+/// Sets up the discriminant for an enum. This includes setting the `repr`, creating a fieldless
+/// version of the enum (even if it's already fieldless for consistency), and implementing
+/// Discriminantable.
+///
+/// Any errors are reported as `compile_error!`s spanned to the offending tokens.
+///
+/// * `attr`: The arguments to the attribute, starting with the repr for the enum if the enum does
+///   not already have one.
+/// * `item`: The enum.
+pub fn discriminant_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    try_discriminant_impl(attr, item).unwrap_or_else(syn::Error::into_compile_error)
+}
+
+/// Sets up the discriminant for an enum. See [`discriminant_impl`].
+///
+/// * `attr`: The arguments to the attribute, starting with the repr for the enum if the enum does
+///   not already have one.
+/// * `item`: The enum.
+///
+/// # Errors
+/// This will fail if
+/// - The arguments to the attribute are invalid or an unsupported repr is used
+/// - `item` cannot be parsed or is invalid in some way (e.g., too many variants for the repr type)
+/// - It is applied any anything besides an enum
+fn try_discriminant_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let options = DiscriminantOptions::parse(attr)?;
+    let mut item: DeriveInput = syn::parse2(item)?;
+    let repr = Repr::resolve(options.repr.as_ref(), &mut item.attrs)?;
+    let repr_attribute = repr.to_attribute();
+    let (generated, discriminants) = generate_discriminant(&item, &options, &repr.primitive)?;
+    remove_retired_variants(&mut item, &discriminants);
+    strip_variant_options(&mut item);
+
+    Ok(quote! {
+        #repr_attribute
+        #item
+
+        #generated
+    })
+}
+
+/// Implements the `Discriminant` derive. This produces the same code as [`discriminant_impl`],
+/// but requires the enum to already have a primitive `repr` since the enum cannot be modified.
+///
+/// Any errors are reported as `compile_error!`s spanned to the offending tokens.
+///
+/// * `item`: The enum, configured by `#[discriminant(..)]` helper attributes.
+pub fn derive_discriminant_impl(item: TokenStream) -> TokenStream {
+    try_derive_discriminant_impl(item).unwrap_or_else(syn::Error::into_compile_error)
+}
+
+/// Implements the `Discriminant` derive. See [`derive_discriminant_impl`].
+///
+/// * `item`: The enum, configured by `#[discriminant(..)]` helper attributes.
+///
+/// # Errors
+/// This will fail if
+/// - The helper attributes are invalid or the enum does not have a supported primitive `repr`
+/// - `item` cannot be parsed or is invalid in some way (e.g., too many variants for the repr type)
+/// - It is applied any anything besides an enum
+fn try_derive_discriminant_impl(item: TokenStream) -> syn::Result<TokenStream> {
+    let item: DeriveInput = syn::parse2(item)?;
+    let options = DiscriminantOptions::from_attributes(&item.attrs)?;
+    let repr = Repr::existing(options.repr.as_ref(), &item.attrs)?;
+    let (generated, discriminants) = generate_discriminant(&item, &options, &repr.primitive)?;
+    if let Data::Enum(e) = &item.data
+        && let Some(retired) = e.variants.iter().find(|variant| discriminants.iter().all(|discriminant| discriminant.variant.ident != variant.ident))
+    {
+        return Err(syn::Error::new_spanned(
+            &retired.ident,
+            "`#[derive(Discriminant)]` cannot remove retired variants. Use the `discriminant` attribute instead",
+        ));
+    }
+    Ok(generated)
+}
+
+/// Generates everything for an enum besides the enum itself: the `discriminant` method, the
+/// `Discriminantable` implementation, the fieldless enum and any optional implementations.
+///
+/// Returns the generated code along with the discriminants of the current (not retired) variants.
+///
+/// * `item`: The enum.
+/// * `options`: The options for the enum.
+/// * `repr`: The primitive representation of the enum.
+///
+/// # Errors
+/// Fails if the enum or its discriminants are invalid, or the options conflict.
+fn generate_discriminant(item: &DeriveInput, options: &DiscriminantOptions, repr: &Ident) -> syn::Result<(TokenStream, Vec<DiscriminantVariant>)> {
+    let (mut discriminants, warnings) = extract_discriminants_for_repr(repr, &item.ident, &enum_variants(item)?, options.explicit())?;
+    mark_retired(&mut discriminants)?;
+    let (retired, mut discriminants): (Vec<_>, Vec<_>) = discriminants.into_iter().partition(|discriminant| discriminant.retired);
+    name_variants(&mut discriminants, options.rename_all)?;
+    let duplicate_assertions = value::check_duplicate_discriminants(&discriminants, &item.ident, repr)?;
+    let reserved = reserved::resolve_reserved(&options.reserved, repr)?;
+    let reserved_assertions = reserved::check_reserved_discriminants(&discriminants, &retired, &reserved, &item.ident, repr)?;
+    let retired_condition = reserved::generate_retired_condition(&retired, &reserved);
+    export::write_exports(&item.ident, repr, &discriminants, &options.export)?;
+    let track_explicit_env = options.track_explicit_env();
+    let lock = match &options.lock {
+        Some(lock_options) => lock::check_lock_file(lock_options, &discriminants, &retired, &item.ident, repr)?,
+        None => quote! {},
+    };
+
+    let item_ident = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let schema_hash_value = schema::schema_hash(item_ident, repr, &discriminants, options.schema_hash.fields);
+    let schema_hash = schema::hash_literal(schema_hash_value);
+    let hashed = if options.schema_hash.fields { "variant names, discriminants and fields" } else { "variant names and discriminants" };
+    let schema_hash_doc = format!("A hash of the name, repr, {hashed} of [{item_ident}], for checking that peers agree on them.");
+
+    let fieldless_ident = options.fieldless.name.clone().unwrap_or_else(|| format_ident!("{item_ident}_"));
+    let other = other_variant(options, &discriminants)?;
+    let fieldless = if options.fieldless.skip {
+        quote! {}
+    } else {
+        let fieldless = generate_fieldless_enum(item, repr, &discriminants, &fieldless_ident, &options.fieldless, other.as_ref(), retired_condition.as_ref());
+        quote! {
+            #fieldless
+
+            impl #fieldless_ident {
+                #[doc = #schema_hash_doc]
+                pub const SCHEMA_HASH: u64 = #schema_hash;
+            }
+
+            impl discrimin_ant::Discriminantable for #fieldless_ident {
+                type Discriminant = #repr;
+                const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+                fn discriminant(&self) -> Self::Discriminant {
+                    self.discriminant()
+                }
+            }
+        }
+    };
+
+    let serde_impls = generate_serialization_impls(item, options, &discriminants, &fieldless_ident, repr, other.as_ref())?;
+
+    let code = match &options.code {
+        Some(_) if options.fieldless.skip => return Err(syn::Error::new(Span::call_site(), "The `code` option requires the fieldless enum. Remove `fieldless(skip)`")),
+        Some(code_options) => {
+            let code_ident = code_options.name.clone().unwrap_or_else(|| format_ident!("{item_ident}Code"));
+            code::generate_code_newtype(item, &discriminants, repr, &code_ident, &fieldless_ident, other.as_ref(), schema_hash_value)?
+        }
+        None => quote! {},
+    };
+
+    let generated = quote! {
+        #warnings
+        #track_explicit_env
+        #duplicate_assertions
+        #reserved_assertions
+        #lock
+
+        impl #impl_generics #item_ident #ty_generics #where_clause {
+            #[doc = #schema_hash_doc]
+            pub const SCHEMA_HASH: u64 = #schema_hash;
+
+            /// Returns the discriminant of [Self].
+            pub const fn discriminant(&self) -> #repr {
+                unsafe { *core::ptr::from_ref::<Self>(self).cast::<#repr>() }
+            }
+        }
+
+        impl #impl_generics discrimin_ant::Discriminantable for #item_ident #ty_generics #where_clause {
+            type Discriminant = #repr;
+            const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+            fn discriminant(&self) -> Self::Discriminant {
+                self.discriminant()
+            }
+        }
+
+        #fieldless
+
+        #code
+
+        #serde_impls
+    };
+    Ok((generated, discriminants))
+}
+
+/// Returns the variants of the enum.
+///
+/// * `item`: The enum.
+///
+/// # Errors
+/// Fails if `item` is not an enum.
+fn enum_variants(item: &DeriveInput) -> syn::Result<Vec<Variant>> {
+    match &item.data {
+        Data::Enum(e) => Ok(e.variants.iter().cloned().collect()),
+        Data::Struct(s) => Err(syn::Error::new(s.struct_token.span, "Only enums are supported by `discriminant`")),
+        Data::Union(u) => Err(syn::Error::new(u.union_token.span, "Only enums are supported by `discriminant`")),
+    }
+}
+
+/// Generates the `serde` and `codec` implementations for the enum and its fieldless version, along
+/// with their `schemars` implementations if requested.
+///
+/// * `item`: The enum.
+/// * `options`: The options for the enum.
+/// * `discriminants`: The discriminants of the current variants.
+/// * `fieldless_ident`: The name of the fieldless enum.
+/// * `repr`: The repr for the enum.
+/// * `other`: The catch-all variant of the fieldless enum, if any.
+///
+/// # Errors
+/// Fails if `serde` or `codec` is requested without the fieldless enum, or `schemars` without
+/// `serde`.
+fn generate_serialization_impls(item: &DeriveInput, options: &DiscriminantOptions, discriminants: &[DiscriminantVariant], fieldless_ident: &Ident, repr: &Ident, other: Option<&OtherVariant>) -> syn::Result<TokenStream> {
+    let codec_impls = if options.codec {
+        if options.fieldless.skip {
+            return Err(syn::Error::new(Span::call_site(), "The `codec` option requires the fieldless enum. Remove `fieldless(skip)`"));
+        }
+        if repr == "usize" || repr == "isize" {
+            return Err(syn::Error::new_spanned(
+                repr,
+                format!("The `codec` option needs a repr with the same width on every target, which `{repr}` does not have. Use a repr such as `u32` or `u64`"),
+            ));
+        }
+        let original_impls = codec::generate_codec_impls(discriminants, &item.ident, &item.generics, fieldless_ident, repr);
+        let fieldless_impls = codec::generate_fieldless_codec_impls(fieldless_ident, repr);
+        quote! {
+            #original_impls
+            #fieldless_impls
+        }
+    } else {
+        quote! {}
+    };
+    let Some(serde_options) = &options.serde else {
+        if options.schemars {
+            return Err(syn::Error::new(Span::call_site(), "The `schemars` option describes the encoding of the `serde` option. Add `serde`"));
+        }
+        return Ok(codec_impls);
+    };
+    if options.fieldless.skip {
+        return Err(syn::Error::new(Span::call_site(), "The `serde` option requires the fieldless enum. Remove `fieldless(skip)`"));
+    }
+    let original_impls = serde::generate_serde_impls(discriminants, &item.ident, &item.generics, fieldless_ident, repr);
+    let fieldless_impls = serde::generate_fieldless_serde_impls(discriminants, fieldless_ident, repr, serde_options, other);
+    let schemars_impls = if options.schemars {
+        let original_impl = schemars::generate_schemars_impl(discriminants, &item.ident, &item.generics, repr);
+        let fieldless_impl = schemars::generate_fieldless_schemars_impl(discriminants, fieldless_ident, repr, serde_options, other);
+        quote! {
+            #original_impl
+            #fieldless_impl
+        }
+    } else {
+        quote! {}
+    };
+    Ok(quote! {
+        #original_impls
+        #fieldless_impls
+        #schemars_impls
+        #codec_impls
+    })
+}
+
+/// Generates the fieldless version of the enum along with its conversions.
+///
+/// * `item`: The original enum.
+/// * `repr`: The repr for the enum.
+/// * `discriminants`: The discriminants of the original enum.
+/// * `fieldless_ident`: The name of the fieldless enum.
+/// * `options`: The options for the fieldless enum.
+/// * `other`: The catch-all variant for unknown discriminants, if requested.
+/// * `retired_condition`: A condition that is true when `value` is retired or reserved, if any are.
+fn generate_fieldless_enum(
+    item: &DeriveInput,
+    repr: &Ident,
+    discriminants: &[DiscriminantVariant],
+    fieldless_ident: &Ident,
+    options: &FieldlessOptions,
+    other: Option<&OtherVariant>,
+    retired_condition: Option<&TokenStream>,
+) -> TokenStream {
+    let item_ident = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    let visibility = options.vis.as_ref().unwrap_or(&item.vis);
+    let derives = options.derives.as_ref().map_or_else(
+        || quote! { #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)] },
+        |derives| if derives.is_empty() { quote! {} } else { quote! { #[derive(#(#derives),*)] } },
+    );
+    let fieldless_variants = generate_fieldless_variants(discriminants, item_ident, repr, other);
+    let fieldless_from_match_arms = generate_original_to_fieldless_match_arms(discriminants, item_ident);
+    let repr_conversion = generate_fieldless_repr_conversion(discriminants, repr, fieldless_ident, options, other, retired_condition);
+    let variant_idents = discriminants.iter().map(|discriminant| &discriminant.variant.ident).collect::<Vec<_>>();
+    let variant_names = discriminants.iter().map(|discriminant| &discriminant.name).collect::<Vec<_>>();
+    let variant_exprs = discriminants.iter().map(|discriminant| &discriminant.expr).collect::<Vec<_>>();
+    let count = proc_macro2::Literal::usize_unsuffixed(discriminants.len());
+
+    // Without a catch-all, the fieldless enum shares the discriminants of the original, so they can
+    // be read directly. The catch-all carries its discriminant, so it is matched on instead.
+    let (repr_attribute, discriminant_values, discriminant_body, other_name_arm, display_body, from_discriminant) = match other {
+        None => (
+            quote! { #[repr(#repr)] },
+            quote! { #(Self::#variant_idents as #repr),* },
+            quote! { unsafe { *core::ptr::from_ref::<Self>(self).cast::<#repr>() } },
+            quote! {},
+            quote! { f.write_str(self.name()) },
+            quote! { .and_then(|discriminant| Self::try_from(discriminant).ok()) },
+        ),
+        Some(OtherVariant { ident: other_ident, name: other_name }) => (
+            quote! {},
+            quote! { #((#variant_exprs)),* },
+            quote! {
+                match *self {
+                    #(Self::#variant_idents => #variant_exprs,)*
+                    Self::#other_ident(value) => value,
+                }
+            },
+            quote! { Self::#other_ident(_) => #other_name, },
+            quote! {
+                match *self {
+                    Self::#other_ident(value) => core::fmt::Display::fmt(&value, f),
+                    _ => f.write_str(self.name()),
+                }
+            },
+            quote! { .map(Self::from) },
+        ),
+    };
+
+    let fieldless_doc = format!("Fieldless representations of [{item_ident}]. Used to extract discriminants without fully constructing the enum.");
+    quote! {
+        #[doc = #fieldless_doc]
+        #derives
+        #repr_attribute
+        #visibility enum #fieldless_ident {
+            #fieldless_variants
+        }
+
+        impl #fieldless_ident {
+            /// Every variant of [Self], in declaration order.
+            pub const VARIANTS: [Self; #count] = [#(Self::#variant_idents),*];
+            /// The number of variants of [Self].
+            pub const COUNT: usize = #count;
+            /// The discriminant of every variant of [Self], in declaration order.
+            pub const DISCRIMINANTS: [#repr; #count] = [#discriminant_values];
+
+            /// Returns the discriminant of [Self].
+            pub const fn discriminant(&self) -> #repr {
+                #discriminant_body
+            }
+
+            /// Returns an iterator over every variant of [Self], in declaration order.
+            pub fn iter() -> core::array::IntoIter<Self, #count> {
+                Self::VARIANTS.into_iter()
+            }
+
+            /// Returns the name of the variant.
+            pub const fn name(&self) -> &'static str {
+                match *self {
+                    #(Self::#variant_idents => #variant_names,)*
+                    #other_name_arm
+                }
+            }
+        }
+
+        impl core::fmt::Display for #fieldless_ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                #display_body
+            }
+        }
+
+        impl core::str::FromStr for #fieldless_ident {
+            type Err = discrimin_ant::ParseVariantError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#variant_names => Ok(Self::#variant_idents),)*
+                    _ => s
+                        .parse::<#repr>()
+                        .ok()
+                        #from_discriminant
+                        .ok_or_else(|| discrimin_ant::ParseVariantError::new(s, stringify!(#fieldless_ident))),
+                }
+            }
+        }
+
+        #repr_conversion
+
+        impl #impl_generics From<&#item_ident #ty_generics> for #fieldless_ident #where_clause {
+            fn from(value: &#item_ident #ty_generics) -> Self {
+                match value {
+                   #fieldless_from_match_arms
+                }
+            }
+        }
+    }
+}
+
+/// Generates the conversion from the repr into the fieldless enum. This is `TryFrom`, unless there
+/// is a catch-all variant, in which case every value converts and `From` is used instead.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `repr`: The repr for the enum.
+/// * `fieldless_ident`: The name of the fieldless enum.
+/// * `options`: The options for the fieldless enum.
+/// * `other`: The catch-all variant for unknown discriminants, if requested.
+/// * `retired_condition`: A condition that is true when `value` is retired or reserved, if any are.
+fn generate_fieldless_repr_conversion(
+    discriminants: &[DiscriminantVariant],
+    repr: &Ident,
+    fieldless_ident: &Ident,
+    options: &FieldlessOptions,
+    other: Option<&OtherVariant>,
+    retired_condition: Option<&TokenStream>,
+) -> TokenStream {
+    if let Some(OtherVariant { ident: other_ident, .. }) = other {
+        let from_variants = generate_fieldless_from_repr_variants(discriminants, |ident| quote! { Self::#ident });
+        return quote! {
+            impl From<#repr> for #fieldless_ident {
+                fn from(value: #repr) -> Self {
+                    #from_variants
+                    Self::#other_ident(value)
+                }
+            }
+        };
+    }
+
+    let try_from_variants = generate_fieldless_try_from_variants(discriminants);
+    let unknown = quote! { discrimin_ant::UnknownDiscriminant::new(value, stringify!(#fieldless_ident)) };
+    let (error, unknown_discriminant) = match (&options.error, options.retired_error) {
+        (Some(error), _) => (quote! { #error }, quote! { <#error>::from(value) }),
+        (None, false) => (quote! { discrimin_ant::UnknownDiscriminant<Self, #repr> }, unknown),
+        (None, true) => {
+            let retired = retired_condition.map_or_else(
+                || quote! {},
+                |condition| {
+                    quote! {
+                        if #condition {
+                            return Err(discrimin_ant::DiscriminantError::Retired(#unknown));
+                        }
+                    }
+                },
+            );
+            (quote! { discrimin_ant::DiscriminantError<Self, #repr> }, quote! { { #retired discrimin_ant::DiscriminantError::Unknown(#unknown) } })
+        }
+    };
+    quote! {
+        impl TryFrom<#repr> for #fieldless_ident {
+            type Error = #error;
+
+            fn try_from(value: #repr) -> Result<Self, Self::Error> {
+                #try_from_variants
+                Err(#unknown_discriminant)
+            }
+        }
+    }
+}
+
+/// Resolves the catch-all variant requested with `other = Ident`.
+///
+/// * `options`: The options for the enum.
+/// * `discriminants`: The discriminants of the enum, after naming.
+///
+/// # Errors
+/// Fails if the catch-all variant clashes with another variant or option.
+fn other_variant(options: &DiscriminantOptions, discriminants: &[DiscriminantVariant]) -> syn::Result<Option<OtherVariant>> {
+    let Some(ident) = &options.other else {
+        return Ok(None);
+    };
+    if options.fieldless.skip {
+        return Err(syn::Error::new_spanned(ident, "The `other` option requires the fieldless enum. Remove `fieldless(skip)`"));
+    }
+    if let Some(error) = &options.fieldless.error {
+        return Err(syn::Error::new_spanned(error, "`fieldless(error)` cannot be used with `other` since every discriminant converts"));
+    }
+    if options.fieldless.retired_error {
+        return Err(syn::Error::new_spanned(ident, "`fieldless(retired_error)` cannot be used with `other` since every discriminant converts"));
+    }
+    let unraw = ident.unraw().to_string();
+    let name = options.rename_all.map_or_else(|| unraw.clone(), |case| case.apply(&unraw));
+    if let Some(existing) = discriminants.iter().find(|discriminant| discriminant.variant.ident == *ident || discriminant.name == name) {
+        return Err(syn::Error::new_spanned(ident, format!("`{ident}` clashes with the variant `{}`", existing.variant.ident)));
+    }
+    Ok(Some(OtherVariant { ident: ident.clone(), name }))
+}
+
+/// Names each variant from its `rename` option or by converting its identifier, without any `r#`
+/// prefix, to the `rename_all` case.
+///
+/// * `discriminants`: The discriminants of the enum.
+/// * `rename_all`: The case to convert the variant names to, if any.
+///
+/// # Errors
+/// Fails if a variant option is invalid or two variants end up with the same name.
+fn name_variants<'a>(discriminants: impl IntoIterator<Item = &'a mut DiscriminantVariant>, rename_all: Option<Case>) -> syn::Result<()> {
+    let mut error: Option<syn::Error> = None;
+    let mut seen: HashMap<String, Ident> = HashMap::new();
+    for discriminant in discriminants {
+        let ident = &discriminant.variant.ident;
+        let options = VariantOptions::from_attributes(&discriminant.variant.attrs)?;
+        discriminant.name = match (options.rename, rename_all) {
+            (Some(rename), _) => rename.value(),
+            (None, Some(case)) => case.apply(&ident.unraw().to_string()),
+            (None, None) => ident.unraw().to_string(),
+        };
+        if let Some(first) = seen.get(&discriminant.name) {
+            let duplicate = syn::Error::new_spanned(ident, format!("`{ident}` has the name `{}`, which is already used by `{first}`", discriminant.name));
+            match &mut error {
+                Some(error) => error.combine(duplicate),
+                None => error = Some(duplicate),
+            }
+        } else {
+            seen.insert(discriminant.name.clone(), ident.clone());
+        }
+    }
+    error.map_or(Ok(()), Err)
+}
+
+/// Marks the variants that have the `retired` option.
+///
+/// * `discriminants`: The discriminants of every variant of the enum.
+///
+/// # Errors
+/// Fails if the options of a variant are invalid.
+fn mark_retired(discriminants: &mut [DiscriminantVariant]) -> syn::Result<()> {
+    for discriminant in discriminants {
+        discriminant.retired = VariantOptions::from_attributes(&discriminant.variant.attrs)?.retired;
+    }
+    Ok(())
+}
+
+/// Removes the retired variants from the enum. Since removing a variant would change the implicit
+/// discriminants of the variants after it, every remaining variant is given its discriminant
+/// explicitly.
+///
+/// * `item`: The enum.
+/// * `discriminants`: The discriminants of the current variants.
+fn remove_retired_variants(item: &mut DeriveInput, discriminants: &[DiscriminantVariant]) {
+    if let Data::Enum(e) = &mut item.data
+        && e.variants.len() != discriminants.len()
+    {
+        e.variants = discriminants
+            .iter()
+            .map(|DiscriminantVariant { variant, expr, .. }| Variant {
+                discriminant: Some((Default::default(), expr.clone())),
+                ..variant.clone()
+            })
+            .collect();
+    }
+}
+
+/// Removes the `#[discriminant(..)]` options from the variants of the enum, since the attribute
+/// macro cannot declare them as helper attributes.
+///
+/// * `item`: The enum.
+fn strip_variant_options(item: &mut DeriveInput) {
+    if let Data::Enum(e) = &mut item.data {
+        for variant in &mut e.variants {
+            variant.attrs.retain(|attr| !attr.path().is_ident("discriminant"));
+        }
+    }
+}
+
+/// Extracts the discriminants for the enum using the integer type named by `repr`.
+///
+/// * `repr`: The repr for the enum.
+/// * `item_ident`: The name of the enum.
+/// * `variants`: The variants of the enum.
+/// * `explicit`: Whether every variant must have an explicit discriminant.
+///
+/// # Errors
+/// Fails if `repr` is not a primitive integer type or the discriminants cannot be extracted.
+fn extract_discriminants_for_repr(repr: &Ident, item_ident: &Ident, variants: &[Variant], explicit: bool) -> syn::Result<(Vec<DiscriminantVariant>, TokenStream)> {
+    let mut warnings = quote! {};
+    let discriminants = match repr.to_string().as_str() {
+        "u8" => extract_enum_discriminants::<u8>(variants, explicit),
+        "u16" => extract_enum_discriminants::<u16>(variants, explicit),
+        "u32" => extract_enum_discriminants::<u32>(variants, explicit),
+        "u64" => extract_enum_discriminants::<u64>(variants, explicit),
+        "u128" => extract_enum_discriminants::<u128>(variants, explicit),
+        "i8" => extract_enum_discriminants::<i8>(variants, explicit),
+        "i16" => extract_enum_discriminants::<i16>(variants, explicit),
+        "i32" => extract_enum_discriminants::<i32>(variants, explicit),
+        "i64" => extract_enum_discriminants::<i64>(variants, explicit),
+        "i128" => extract_enum_discriminants::<i128>(variants, explicit),
+        "usize" => {
+            warnings = generate_warning(&format!("The {repr} discriminant type varies in size between architectures. It is recommended to use fixed-length data types."), repr.span());
+            extract_enum_discriminants::<usize>(variants, explicit)
+        }
+        "isize" => {
+            warnings = generate_warning(&format!("The {repr} discriminant type varies in size between architectures. It is recommended to use fixed-length data types."), repr.span());
+            extract_enum_discriminants::<isize>(variants, explicit)
+        }
+        _ => {
+            return Err(syn::Error::new(
+                repr.span(),
+                format!("Unsupported discriminant type `{repr}` for `{item_ident}`. Must be a primitive integer type. See <https://doc.rust-lang.org/reference/type-layout.html#primitive-representations>"),
+            ));
+        }
+    }?;
+    Ok((discriminants, warnings))
+}
+
+/// Generates code that causes the compiler to emit a warning at `span`.
+///
+/// Proc macros cannot emit warnings on stable, so this references a deprecated item instead.
+///
+/// * `message`: The message for the warning.
+/// * `span`: Where the warning should point.
+pub fn generate_warning(message: &str, span: Span) -> TokenStream {
+    let warning = Ident::new("discriminant_warning", span);
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const #warning: () = ();
+            #warning
+        };
+    }
+}
+
+/// Extracts and calculates the discriminants for the enum.
+///
+/// * `variants`: The variants of the enum.
+/// * `explicit`: Whether every variant must have an explicit discriminant.
+///
+/// # Errors
+/// Fails if a discriminant cannot fit in `T`, or a variant is missing an explicit discriminant
+/// when `explicit` is set.
+fn extract_enum_discriminants<T: PrimInt + ToTokens + core::fmt::Display>(variants: &[Variant], explicit: bool) -> syn::Result<Vec<DiscriminantVariant>> {
+    let mut disciminant_offset = Some(T::zero());
+    let mut current_discriminant_expr: Option<Expr> = None;
+    let mut current_discriminant_value: Option<T> = Some(T::zero());
+    variants
+        .iter()
+        .map(|variant| {
+            let (discriminant_expr, discriminant_value) = if let Some((_, expr)) = &variant.discriminant {
+                disciminant_offset = Some(T::one());
+                current_discriminant_expr = Some(expr.clone());
+                current_discriminant_value = value::fold_discriminant::<T>(expr)?;
+                (expr.clone(), current_discriminant_value)
+            } else {
+                let Some(offset) = disciminant_offset else {
+                    return Err(syn::Error::new_spanned(
+                        &variant.ident,
+                        format!("Too many variants! `{}` does not fit in the discriminant type `{}`", variant.ident, core::any::type_name::<T>()),
+                    ));
+                };
+                let expr = current_discriminant_expr
+                    .as_ref()
+                    .map_or_else(|| parse_quote!(#offset), |current_expr| parse_quote!(#current_expr + #offset));
+                let value = match current_discriminant_value {
+                    Some(current_value) if current_discriminant_expr.is_some() => Some(current_value.checked_add(&offset).ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            &variant.ident,
+                            format!("The implicit discriminant of `{}` overflows `{}`", variant.ident, core::any::type_name::<T>()),
+                        )
+                    })?),
+                    Some(_) => Some(offset),
+                    None => None,
+                };
+                if explicit {
+                    let suggestion = value.map_or_else(|| quote!(#expr).to_string(), |value| value.to_string());
+                    return Err(syn::Error::new_spanned(
+                        &variant.ident,
+                        format!("`{}` must have an explicit discriminant. Add `= {suggestion}` to keep its current value", variant.ident),
+                    ));
+                }
+                disciminant_offset = offset.checked_add(&T::one());
+                (expr, value)
+            };
+            Ok(DiscriminantVariant {
+                variant: variant.clone(),
+                expr: discriminant_expr,
+                value: discriminant_value.map(DiscriminantValue::from_int),
+                name: variant.ident.to_string(),
+                retired: false,
+            })
+        })
+        .collect()
+}
+
+/// Generates the body of `TryFrom<repr>` for the fieldless enum.
+///
+/// The body returns the variant that has the discriminant `value`, and falls through if none do,
+/// so it should be followed by the error to return.
+///
+/// * `discriminants`: The discriminants of the original enum.
+pub fn generate_fieldless_try_from_variants(discriminants: &[DiscriminantVariant]) -> TokenStream {
+    generate_fieldless_from_repr_variants(discriminants, |ident| quote! { Ok(Self::#ident) })
+}
+
+/// Generates code to transform the repr into the the fieldless enum. For use with `TryFrom` or
+/// `From`.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `wrap`: Wraps the fieldless variant into the value to return.
+pub fn generate_fieldless_from_repr_variants(discriminants: &[DiscriminantVariant], wrap: impl Fn(&Ident) -> TokenStream) -> TokenStream {
+    let mut from_variants = quote! {};
+    for DiscriminantVariant { variant, expr, .. } in discriminants {
+        let result = wrap(&variant.ident);
+        from_variants = quote! {
+            #from_variants
+            if value == (#expr) {
+                return #result;
+            }
+        };
+    }
+    from_variants
+}
+
+/// Generates the fieldless variants of the original enum. Shall have the same variants and
+/// discriminants, just with all variants as units.
+///
+/// With a catch-all variant, the fieldless enum cannot have explicit discriminants, so they are
+/// left off and the catch-all variant is added at the end.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `item_ident`: The name of the original enum.
+/// * `repr`: The repr for the enum.
+/// * `other`: The catch-all variant for unknown discriminants, if requested.
+pub fn generate_fieldless_variants(discriminants: &[DiscriminantVariant], item_ident: &syn::Ident, repr: &Ident, other: Option<&OtherVariant>) -> TokenStream {
+    let mut fieldless_variants = quote! {};
+    for DiscriminantVariant { variant, expr, .. } in discriminants {
+        let ident = variant.ident.clone();
+        let doc = format!("A fieldless version of [{item_ident}::{ident}], used to extract the variant's discriminant without needing to fully construct it.");
+        let discriminant = if other.is_some() { quote! {} } else { quote! { = #expr } };
+        fieldless_variants = quote! {
+            #fieldless_variants
+            #[doc = #doc]
+            #ident #discriminant,
+        };
+    }
+    if let Some(OtherVariant { ident, .. }) = other {
+        let doc = format!("A discriminant that does not belong to any variant of [{item_ident}]. Kept so that it can be passed on unchanged.");
+        fieldless_variants = quote! {
+            #fieldless_variants
+            #[doc = #doc]
+            #ident(#repr),
+        };
+    }
+    fieldless_variants
+}
+
+/// Generates the match arms to map the original enum to the fieldless enum.
+///
+/// * `discriminants`: The discriminants for the original enum.
+/// * `enum_name`: The name of the original enum.
+pub fn generate_original_to_fieldless_match_arms(discriminants: &[DiscriminantVariant], enum_name: &Ident) -> TokenStream {
+    if discriminants.is_empty() {
+        return quote! {
+            _ => unreachable!("Only reachable with a zero-variant enum")
+        };
+    }
+
+    let mut match_arms = quote! {};
+    for DiscriminantVariant { variant, .. } in discriminants {
+        let ident = variant.ident.clone();
+        match_arms = match &variant.fields {
+            syn::Fields::Named(_) => quote! {
+                #match_arms
+                #enum_name::#ident{ .. } => Self::#ident,
+            },
+            syn::Fields::Unnamed(_) => quote! {
+                #match_arms
+                #enum_name::#ident(..) => Self::#ident,
+            },
+            syn::Fields::Unit => quote! {
+                #match_arms
+                #enum_name::#ident => Self::#ident,
+            },
+        };
+    }
+    match_arms
+}
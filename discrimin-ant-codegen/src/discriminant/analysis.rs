@@ -0,0 +1,149 @@
+//! Resolves the discriminant of every variant of an enum the same way as the macros, without
+//! generating any code. This lets tools read the numbering of an enum straight from its source.
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{DeriveInput, Expr, Fields, Ident, Variant};
+
+use super::options::DiscriminantOptions;
+use super::repr::Repr;
+use super::schema::canonical_tokens;
+use super::value::DiscriminantValue;
+use super::{enum_variants, extract_discriminants_for_repr, mark_retired, name_variants, reserved, value};
+
+/// An enum along with the discriminants that the `discriminant` macros give its variants.
+#[derive(Clone)]
+pub struct DiscriminantEnum {
+    /// The name of the enum.
+    pub ident: Ident,
+    /// The repr for the enum, e.g. `u8`.
+    pub repr: Ident,
+    /// Every variant of the enum in declaration order, including retired ones.
+    pub variants: Vec<DiscriminantVariant>,
+}
+
+/// A variant of an enum along with its discriminant.
+#[derive(Clone)]
+pub struct DiscriminantVariant {
+    /// The variant as declared, including its attributes.
+    pub variant: Variant,
+    /// The expression for the discriminant. This is either the explicit discriminant of the
+    /// variant or an offset from the last explicit discriminant.
+    pub expr: Expr,
+    /// The value of the discriminant, if it could be evaluated at macro time. This is `None` when
+    /// the discriminant depends on something like a constant.
+    pub value: Option<DiscriminantValue>,
+    /// The name of the variant after any renames, used for `Display` and `FromStr`.
+    pub name: String,
+    /// Whether the variant is marked `#[discriminant(retired)]`.
+    pub retired: bool,
+}
+
+/// The fields of a variant, with each type written as in Rust, e.g. `Vec<u8>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldShape {
+    /// A variant without fields.
+    Unit,
+    /// A variant with unnamed fields, e.g. `Data(u32)`.
+    Unnamed(Vec<String>),
+    /// A variant with named fields, e.g. `Move { x: i32 }`.
+    Named(Vec<(String, String)>),
+}
+
+impl DiscriminantEnum {
+    /// Resolves the discriminants of an enum with the `discriminant` attribute.
+    ///
+    /// * `attr`: The arguments to the attribute, starting with the repr for the enum if the enum
+    ///   does not already have one.
+    /// * `item`: The enum, without the `discriminant` attribute itself.
+    ///
+    /// # Errors
+    /// Fails wherever the `discriminant` attribute would, such as for invalid arguments, an
+    /// unsupported repr, a discriminant that does not fit in the repr or a duplicate, retired or
+    /// reserved discriminant.
+    pub fn from_attribute(attr: TokenStream, item: &DeriveInput) -> syn::Result<Self> {
+        let options = DiscriminantOptions::parse(attr)?;
+        let repr = Repr::resolve(options.repr.as_ref(), &mut item.attrs.clone())?;
+        Self::new(item, &options, repr.primitive)
+    }
+
+    /// Resolves the discriminants of an enum with `#[derive(Discriminant)]`.
+    ///
+    /// * `item`: The enum, configured by `#[discriminant(..)]` helper attributes.
+    ///
+    /// # Errors
+    /// Fails wherever the `Discriminant` derive would, such as for invalid helper attributes, a
+    /// missing `repr`, a discriminant that does not fit in the repr or a duplicate, retired or
+    /// reserved discriminant.
+    pub fn from_derive(item: &DeriveInput) -> syn::Result<Self> {
+        let options = DiscriminantOptions::from_attributes(&item.attrs)?;
+        let repr = Repr::existing(options.repr.as_ref(), &item.attrs)?;
+        Self::new(item, &options, repr.primitive)
+    }
+
+    /// Resolves the discriminants of an enum once its options and repr are known.
+    ///
+    /// * `item`: The enum.
+    /// * `options`: The options for the enum.
+    /// * `repr`: The primitive representation of the enum.
+    ///
+    /// Discriminants that were evaluated are checked against each other and the reserved ones. The
+    /// rest can only be checked by compiling the crate.
+    ///
+    /// # Errors
+    /// Fails if the enum or its discriminants are invalid.
+    fn new(item: &DeriveInput, options: &DiscriminantOptions, repr: Ident) -> syn::Result<Self> {
+        let (mut variants, _) = extract_discriminants_for_repr(&repr, &item.ident, &enum_variants(item)?, options.explicit())?;
+        mark_retired(&mut variants)?;
+        name_variants(variants.iter_mut().filter(|variant| !variant.retired), options.rename_all)?;
+        let (retired, current): (Vec<_>, Vec<_>) = variants.iter().cloned().partition(|variant| variant.retired);
+        value::check_duplicate_discriminants(&current, &item.ident, &repr)?;
+        let reserved = reserved::resolve_reserved(&options.reserved, &repr)?;
+        reserved::check_reserved_discriminants(&current, &retired, &reserved, &item.ident, &repr)?;
+        Ok(Self {
+            ident: item.ident.clone(),
+            repr,
+            variants,
+        })
+    }
+
+    /// Returns the variants that are not retired, which are the ones the generators expect.
+    pub fn current_variants(&self) -> Vec<DiscriminantVariant> {
+        self.variants.iter().filter(|variant| !variant.retired).cloned().collect()
+    }
+}
+
+impl DiscriminantVariant {
+    /// Returns the name of the variant as declared.
+    pub const fn ident(&self) -> &Ident {
+        &self.variant.ident
+    }
+
+    /// Describes the fields of the variant.
+    pub fn shape(&self) -> FieldShape {
+        FieldShape::new(&self.variant.fields)
+    }
+}
+
+impl FieldShape {
+    /// Describes the fields of a variant.
+    ///
+    /// * `fields`: The fields of the variant.
+    pub fn new(fields: &Fields) -> Self {
+        let ty = |field: &syn::Field| canonical_tokens(field.ty.to_token_stream());
+        match fields {
+            Fields::Unit => Self::Unit,
+            Fields::Unnamed(fields) => Self::Unnamed(fields.unnamed.iter().map(ty).collect()),
+            Fields::Named(fields) => Self::Named(fields.named.iter().map(|field| (field.ident.as_ref().map(ToString::to_string).unwrap_or_default(), ty(field))).collect()),
+        }
+    }
+
+    /// Writes the fields as they appear in Rust after the variant name, e.g. `(u32, String)`,
+    /// ` { x: i32, y: i32 }` or nothing for a unit variant.
+    pub fn to_rust(&self) -> String {
+        match self {
+            Self::Unit => String::new(),
+            Self::Unnamed(types) => format!("({})", types.join(", ")),
+            Self::Named(fields) => format!(" {{ {} }}", fields.iter().map(|(name, ty)| format!("{name}: {ty}")).collect::<Vec<_>>().join(", ")),
+        }
+    }
+}
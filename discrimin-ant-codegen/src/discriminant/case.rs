@@ -0,0 +1,89 @@
+//! Case conversion for the names of variants, e.g. `rename_all = "snake_case"`.
+use syn::LitStr;
+
+/// A case that variant names can be converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// `snake_case`
+    Snake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+}
+
+impl Case {
+    /// Parses a case from its name, e.g. `"kebab-case"`.
+    ///
+    /// * `lit`: The name of the case.
+    ///
+    /// # Errors
+    /// Fails if the case is not supported.
+    pub fn parse(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "snake_case" => Ok(Self::Snake),
+            "kebab-case" => Ok(Self::Kebab),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            other => Err(syn::Error::new(
+                lit.span(),
+                format!("Unsupported case `{other}`. Expected one of `snake_case`, `kebab-case` or `SCREAMING_SNAKE_CASE`"),
+            )),
+        }
+    }
+
+    /// Converts a `PascalCase` variant name into this case.
+    ///
+    /// * `name`: The name of the variant.
+    pub fn apply(self, name: &str) -> String {
+        let (separator, uppercase) = match self {
+            Self::Snake => ('_', false),
+            Self::Kebab => ('-', false),
+            Self::ScreamingSnake => ('_', true),
+        };
+        let words = split_words(name);
+        let mut converted = String::with_capacity(name.len().saturating_add(words.len()));
+        for (i, word) in words.iter().enumerate() {
+            if i > 0 {
+                converted.push(separator);
+            }
+            if uppercase {
+                converted.push_str(&word.to_uppercase());
+            } else {
+                converted.push_str(&word.to_lowercase());
+            }
+        }
+        converted
+    }
+}
+
+/// Splits a `PascalCase` name into its words. A run of capitals is treated as a single word, so
+/// `HTTPRequest` becomes `HTTP` and `Request`. Digits stay with the word before them.
+///
+/// * `name`: The name to split.
+fn split_words(name: &str) -> Vec<&str> {
+    let chars = name.char_indices().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, &(index, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if start < index {
+                words.push(&name[start..index]);
+            }
+            start = index.saturating_add(c.len_utf8());
+            continue;
+        }
+        let Some(&(_, previous)) = i.checked_sub(1).and_then(|previous| chars.get(previous)) else {
+            continue;
+        };
+        let next_is_lowercase = chars.get(i.saturating_add(1)).is_some_and(|&(_, next)| next.is_lowercase());
+        let starts_word = c.is_uppercase() && (previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lowercase));
+        if starts_word && start < index {
+            words.push(&name[start..index]);
+            start = index;
+        }
+    }
+    if start < name.len() {
+        words.push(&name[start..]);
+    }
+    words
+}
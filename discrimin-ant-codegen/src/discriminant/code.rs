@@ -0,0 +1,162 @@
+//! Generates an open newtype over the repr, e.g. `pub struct MessageCode(pub u8)`, with an
+//! associated constant for each variant.
+//!
+//! Unlike the fieldless enum, any value of the repr is a valid code, which suits FFI and protocols
+//! where unknown discriminants must be carried through untouched.
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Ident};
+
+use super::case::Case;
+use super::schema;
+use super::{DiscriminantVariant, OtherVariant};
+
+/// Generates the newtype along with its conversions.
+///
+/// * `item`: The original enum.
+/// * `discriminants`: The discriminants of the original enum.
+/// * `repr`: The repr for the enum.
+/// * `code_ident`: The name of the newtype.
+/// * `fieldless_ident`: The name of the fieldless enum.
+/// * `other`: The catch-all variant of the fieldless enum, if any.
+/// * `schema_hash`: The schema hash of the original enum, given to the newtype as its own
+///   `SCHEMA_HASH`.
+///
+/// # Errors
+/// Fails if two variants produce the same constant name.
+pub fn generate_code_newtype(item: &DeriveInput, discriminants: &[DiscriminantVariant], repr: &Ident, code_ident: &Ident, fieldless_ident: &Ident, other: Option<&OtherVariant>, schema_hash: u64) -> syn::Result<TokenStream> {
+    let item_ident = &item.ident;
+    let visibility = &item.vis;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let constants = constant_idents(discriminants)?;
+    let constant_docs = discriminants.iter().map(|discriminant| format!("The discriminant of [{item_ident}::{}].", discriminant.variant.ident));
+    let variant_names = discriminants.iter().map(|discriminant| &discriminant.name).collect::<Vec<_>>();
+    let exprs = discriminants.iter().map(|discriminant| &discriminant.expr);
+
+    let to_fieldless = if other.is_some() {
+        quote! {
+            impl From<#code_ident> for #fieldless_ident {
+                fn from(value: #code_ident) -> Self {
+                    Self::from(value.0)
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl TryFrom<#code_ident> for #fieldless_ident {
+                type Error = <Self as TryFrom<#repr>>::Error;
+
+                fn try_from(value: #code_ident) -> Result<Self, Self::Error> {
+                    Self::try_from(value.0)
+                }
+            }
+        }
+    };
+
+    let schema_hash_doc = format!("The schema hash of [{item_ident}], for checking that peers agree on it.");
+    let schema_hash = schema::hash_literal(schema_hash);
+    let code_doc = format!("An open version of [{item_ident}] that can hold any `{repr}`, with a constant for the discriminant of each variant.");
+    Ok(quote! {
+        #[doc = #code_doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[repr(transparent)]
+        #visibility struct #code_ident(pub #repr);
+
+        impl #code_ident {
+            #[doc = #schema_hash_doc]
+            pub const SCHEMA_HASH: u64 = #schema_hash;
+
+            #(
+                #[doc = #constant_docs]
+                pub const #constants: Self = Self(#exprs);
+            )*
+
+            /// Returns the discriminant held by [Self].
+            pub const fn discriminant(self) -> #repr {
+                self.0
+            }
+
+            /// Returns the name of the variant with this discriminant, or `None` if no variant has it.
+            pub const fn name(self) -> Option<&'static str> {
+                #(
+                    if self.0 == Self::#constants.0 {
+                        return Some(#variant_names);
+                    }
+                )*
+                None
+            }
+        }
+
+        impl core::fmt::Debug for #code_ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self.name() {
+                    Some(name) => f.write_str(name),
+                    None => f.debug_tuple(stringify!(#code_ident)).field(&self.0).finish(),
+                }
+            }
+        }
+
+        impl discrimin_ant::Discriminantable for #code_ident {
+            type Discriminant = #repr;
+            const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+            fn discriminant(&self) -> Self::Discriminant {
+                self.0
+            }
+        }
+
+        impl From<#repr> for #code_ident {
+            fn from(value: #repr) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<#code_ident> for #repr {
+            fn from(value: #code_ident) -> Self {
+                value.0
+            }
+        }
+
+        impl From<#fieldless_ident> for #code_ident {
+            fn from(value: #fieldless_ident) -> Self {
+                Self(value.discriminant())
+            }
+        }
+
+        #to_fieldless
+
+        impl #impl_generics From<&#item_ident #ty_generics> for #code_ident #where_clause {
+            fn from(value: &#item_ident #ty_generics) -> Self {
+                Self(value.discriminant())
+            }
+        }
+    })
+}
+
+/// Creates the name of the constant for each variant by converting it, without any `r#` prefix, to
+/// `SCREAMING_SNAKE_CASE`.
+///
+/// * `discriminants`: The discriminants of the original enum.
+///
+/// # Errors
+/// Fails if a constant name is not a valid identifier or two variants produce the same one.
+fn constant_idents(discriminants: &[DiscriminantVariant]) -> syn::Result<Vec<Ident>> {
+    let mut seen: HashMap<String, &Ident> = HashMap::new();
+    discriminants
+        .iter()
+        .map(|DiscriminantVariant { variant, .. }| {
+            let name = Case::ScreamingSnake.apply(&variant.ident.unraw().to_string());
+            if syn::parse_str::<Ident>(&name).is_err() {
+                return Err(syn::Error::new_spanned(&variant.ident, format!("`{}` produces the constant `{name}`, which is not a valid identifier", variant.ident)));
+            }
+            if let Some(first) = seen.insert(name.clone(), &variant.ident) {
+                return Err(syn::Error::new_spanned(&variant.ident, format!("`{}` and `{first}` both produce the constant `{name}`", variant.ident)));
+            }
+            Ok(format_ident!("{name}", span = variant.ident.span()))
+        })
+        .collect()
+}
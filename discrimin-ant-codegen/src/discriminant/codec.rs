@@ -0,0 +1,102 @@
+//! Generates `discrimin_ant::Encode` and `discrimin_ant::Decode` implementations that write an enum
+//! to a byte buffer without `serde` or an allocator.
+//!
+//! Each variant is written as its discriminant at the width of the repr, followed by each of the
+//! variant's fields in declaration order. This is the same layout as the `serde` option produces
+//! with a fixed-width format like `bincode`.
+//!
+//! The fieldless enum is written as its discriminant alone.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, parse_quote};
+
+use super::DiscriminantVariant;
+use super::serde::{field_bindings, variant_pattern, with_type_param_bounds};
+
+/// Generates `Encode` and `Decode` for the original enum.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `item_ident`: The name of the original enum.
+/// * `generics`: The generics of the original enum. Each type parameter is required to implement
+///   the respective trait.
+/// * `fieldless_ident`: The name of the fieldless enum, whose `TryFrom` selects the variant when
+///   decoding.
+/// * `repr`: The repr for the enum.
+pub fn generate_codec_impls(discriminants: &[DiscriminantVariant], item_ident: &Ident, generics: &Generics, fieldless_ident: &Ident, repr: &Ident) -> TokenStream {
+    let mut encode_arms = quote! {};
+    let mut decode_arms = quote! {};
+    for DiscriminantVariant { variant, .. } in discriminants {
+        let ident = &variant.ident;
+        let bindings = field_bindings(&variant.fields);
+        let pattern = variant_pattern(&variant.fields, &bindings);
+        encode_arms = quote! {
+            #encode_arms
+            Self::#ident #pattern => {
+                #(encoder.encode(#bindings)?;)*
+            }
+        };
+        decode_arms = quote! {
+            #decode_arms
+            Ok(#fieldless_ident::#ident) => {
+                #(let #bindings = decoder.decode()?;)*
+                Self::#ident #pattern
+            }
+        };
+    }
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let encode_generics = with_type_param_bounds(generics.clone(), &parse_quote!(discrimin_ant::Encode));
+    let (encode_impl_generics, _, encode_where_clause) = encode_generics.split_for_impl();
+    let decode_generics = with_type_param_bounds(generics.clone(), &parse_quote!(discrimin_ant::Decode));
+    let (decode_impl_generics, _, decode_where_clause) = decode_generics.split_for_impl();
+
+    quote! {
+        impl #encode_impl_generics discrimin_ant::Encode for #item_ident #ty_generics #encode_where_clause {
+            fn encode(&self, buffer: &mut [u8], endian: discrimin_ant::Endian) -> Result<usize, discrimin_ant::EncodeError> {
+                let mut encoder = discrimin_ant::Encoder::new(buffer, endian);
+                encoder.encode(&self.discriminant())?;
+                match self {
+                    #encode_arms
+                }
+                Ok(encoder.position())
+            }
+        }
+
+        impl #decode_impl_generics discrimin_ant::Decode for #item_ident #ty_generics #decode_where_clause {
+            fn decode(buffer: &[u8], endian: discrimin_ant::Endian) -> Result<(Self, usize), discrimin_ant::DecodeError> {
+                let mut decoder = discrimin_ant::Decoder::new(buffer, endian);
+                let discriminant: #repr = decoder.decode()?;
+                let value = match #fieldless_ident::try_from(discriminant) {
+                    #decode_arms
+                    _ => {
+                        return Err(discrimin_ant::DecodeError::new(0, discrimin_ant::DecodeErrorKind::UnknownDiscriminant { enum_name: stringify!(#item_ident), value: discrimin_ant::DecodedDiscriminant::from(discriminant) }));
+                    }
+                };
+                Ok((value, decoder.position()))
+            }
+        }
+    }
+}
+
+/// Generates `Encode` and `Decode` for the fieldless enum.
+///
+/// * `fieldless_ident`: The name of the fieldless enum.
+/// * `repr`: The repr for the enum.
+pub fn generate_fieldless_codec_impls(fieldless_ident: &Ident, repr: &Ident) -> TokenStream {
+    quote! {
+        impl discrimin_ant::Encode for #fieldless_ident {
+            fn encode(&self, buffer: &mut [u8], endian: discrimin_ant::Endian) -> Result<usize, discrimin_ant::EncodeError> {
+                discrimin_ant::Encode::encode(&self.discriminant(), buffer, endian)
+            }
+        }
+
+        impl discrimin_ant::Decode for #fieldless_ident {
+            fn decode(buffer: &[u8], endian: discrimin_ant::Endian) -> Result<(Self, usize), discrimin_ant::DecodeError> {
+                let (discriminant, read) = <#repr as discrimin_ant::Decode>::decode(buffer, endian)?;
+                let value = Self::try_from(discriminant)
+                    .map_err(|_| discrimin_ant::DecodeError::new(0, discrimin_ant::DecodeErrorKind::UnknownDiscriminant { enum_name: stringify!(#fieldless_ident), value: discrimin_ant::DecodedDiscriminant::from(discriminant) }))?;
+                Ok((value, read))
+            }
+        }
+    }
+}
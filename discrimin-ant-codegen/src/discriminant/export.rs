@@ -0,0 +1,227 @@
+//! Exports the discriminants of an enum for use outside of Rust, e.g. `c_header`, `proto`,
+//! `typescript` or `python`.
+//!
+//! The exported files are written to the `discrimin-ant` directory of `OUT_DIR`, which Cargo sets
+//! for packages that have a build script. Files are named after the enum unless given a name with
+//! `export_name`, so two enums with the same name in different modules would write the same file.
+//! The first line of each file names the source file of the enum, and exporting over a file that
+//! an enum in another source file wrote is an error rather than a silent overwrite.
+mod c_header;
+mod proto;
+mod python;
+mod typescript;
+
+use std::path::{Path, PathBuf};
+
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::{Ident, LitStr};
+
+use super::DiscriminantVariant;
+use super::analysis::FieldShape;
+use super::case::Case;
+use super::options::ExportOptions;
+use super::value::DiscriminantValue;
+
+pub use c_header::render_c_header;
+pub use proto::render_proto;
+pub use python::render_python;
+pub use typescript::render_typescript;
+
+/// An enum whose discriminants have all been evaluated, ready to be exported.
+pub struct ExportEnum {
+    /// The name of the enum.
+    pub name: String,
+    /// The repr for the enum, e.g. `u8`.
+    pub repr: String,
+    /// The variants of the enum, in declaration order.
+    pub variants: Vec<ExportVariant>,
+    /// The file the enum is declared in, relative to the crate's manifest, if known. It is named
+    /// in the first line of each export so that two enums with the same name can be told apart.
+    pub source: Option<String>,
+}
+
+/// A variant of an [`ExportEnum`].
+pub struct ExportVariant {
+    /// The name of the variant.
+    pub name: String,
+    /// The discriminant of the variant.
+    pub value: DiscriminantValue,
+    /// The fields of the variant.
+    pub fields: FieldShape,
+}
+
+impl ExportEnum {
+    /// Builds the enum to export from its resolved discriminants.
+    ///
+    /// * `item_ident`: The name of the enum.
+    /// * `repr`: The repr for the enum.
+    /// * `discriminants`: The discriminants of the current variants.
+    /// * `option`: The option that requested the export, used for errors.
+    ///
+    /// # Errors
+    /// Fails if a discriminant cannot be evaluated at macro time.
+    pub fn new(item_ident: &Ident, repr: &Ident, discriminants: &[DiscriminantVariant], option: &str) -> syn::Result<Self> {
+        let variants = discriminants
+            .iter()
+            .map(|DiscriminantVariant { variant, value, .. }| {
+                let value = value.ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &variant.ident,
+                        format!("`{option}` needs the discriminant of `{}` at macro time. Use an integer literal or arithmetic on integer literals", variant.ident),
+                    )
+                })?;
+                Ok(ExportVariant {
+                    name: variant.ident.unraw().to_string(),
+                    value,
+                    fields: FieldShape::new(&variant.fields),
+                })
+            })
+            .collect::<syn::Result<_>>()?;
+        Ok(Self {
+            name: item_ident.unraw().to_string(),
+            repr: repr.to_string(),
+            variants,
+            source: None,
+        })
+    }
+
+    /// Describes where the enum comes from for the first line of an export, e.g.
+    /// `` `Message` in `src/net.rs` ``.
+    pub fn origin(&self) -> String {
+        self.source.as_ref().map_or_else(|| format!("`{}`", self.name), |source| format!("`{}` in `{source}`", self.name))
+    }
+}
+
+/// Writes every export requested for the enum to the `discrimin-ant` directory of `OUT_DIR`.
+///
+/// * `item_ident`: The name of the enum.
+/// * `repr`: The repr for the enum.
+/// * `discriminants`: The discriminants of the current variants.
+/// * `options`: The exports to write.
+///
+/// # Errors
+/// Fails if the enum cannot be exported or a file cannot be written.
+pub fn write_exports(item_ident: &Ident, repr: &Ident, discriminants: &[DiscriminantVariant], options: &ExportOptions) -> syn::Result<()> {
+    let source = source_file(item_ident.span());
+    write_exports_to(std::env::var_os("OUT_DIR").as_deref().map(Path::new), source.as_deref(), item_ident, repr, discriminants, options)
+}
+
+/// Writes every export requested for the enum to the `discrimin-ant` directory of `out_dir`.
+///
+/// * `out_dir`: The value of `OUT_DIR`, if set.
+/// * `source`: The file the enum is declared in, relative to the crate's manifest, if known.
+/// * `item_ident`: The name of the enum.
+/// * `repr`: The repr for the enum.
+/// * `discriminants`: The discriminants of the current variants.
+/// * `options`: The exports to write.
+///
+/// # Errors
+/// Fails if the enum cannot be exported, a file cannot be written, or an enum in another file
+/// already exported a file with the same name.
+pub fn write_exports_to(out_dir: Option<&Path>, source: Option<&str>, item_ident: &Ident, repr: &Ident, discriminants: &[DiscriminantVariant], options: &ExportOptions) -> syn::Result<()> {
+    let export = |option| {
+        ExportEnum::new(item_ident, repr, discriminants, option).map(|export| ExportEnum {
+            source: source.map(str::to_string),
+            ..export
+        })
+    };
+    let name = options.name.as_ref().map_or_else(|| item_ident.unraw().to_string(), LitStr::value);
+    if options.c_header {
+        let header = render_c_header(&export("c_header")?).map_err(|error| syn::Error::new_spanned(repr, error))?;
+        write_export(out_dir, &format!("{name}.h"), &header, "c_header", item_ident)?;
+    }
+    if let Some(proto_options) = &options.proto {
+        let package = proto_options.package.as_ref().map(LitStr::value);
+        let proto = render_proto(&export("proto")?, package.as_deref()).map_err(|error| syn::Error::new_spanned(item_ident, error))?;
+        write_export(out_dir, &format!("{name}.proto"), &proto, "proto", item_ident)?;
+    }
+    if options.typescript {
+        let typescript = render_typescript(&export("typescript")?).map_err(|error| syn::Error::new_spanned(item_ident, error))?;
+        write_export(out_dir, &format!("{name}.ts"), &typescript, "typescript", item_ident)?;
+    }
+    if options.python {
+        let python = render_python(&export("python")?).map_err(|error| syn::Error::new_spanned(item_ident, error))?;
+        let module = options.name.as_ref().map_or_else(|| Case::Snake.apply(&item_ident.unraw().to_string()), LitStr::value);
+        write_export(out_dir, &format!("{module}.py"), &python, "python", item_ident)?;
+    }
+    Ok(())
+}
+
+/// Returns the file that a span is in, relative to the manifest of the crate being compiled if it
+/// is inside it, or `None` if the compiler does not say.
+///
+/// * `span`: The span, such as the name of the enum.
+fn source_file(span: Span) -> Option<String> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    if let Some(local_file) = span.local_file() {
+        let relative = manifest_dir.as_deref().and_then(|dir| local_file.strip_prefix(dir).ok()).unwrap_or(&local_file);
+        return Some(relative.to_string_lossy().replace('\\', "/"));
+    }
+    let file = span.file();
+    (!file.starts_with('<')).then_some(file)
+}
+
+/// Writes an exported file to the `discrimin-ant` directory of `out_dir`. The file is left alone
+/// if it already has the same contents, so that its timestamp does not change.
+///
+/// The first line of each export names the enum and the file it is declared in. If an existing
+/// file was exported from another source file that still declares an enum of the same name, two
+/// enums are exporting to the same file and neither wins. Otherwise the enum was edited or moved
+/// and the file is replaced.
+///
+/// * `out_dir`: The value of `OUT_DIR`, if set.
+/// * `file_name`: The name of the file, e.g. `Message.h`.
+/// * `contents`: The contents of the file.
+/// * `option`: The option that requested the export, used for errors.
+/// * `item_ident`: The name of the enum.
+///
+/// # Errors
+/// Fails if `out_dir` is not set, the file cannot be written, or another enum exported it.
+fn write_export(out_dir: Option<&Path>, file_name: &str, contents: &str, option: &str, item_ident: &Ident) -> syn::Result<()> {
+    let Some(out_dir) = out_dir else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("The `{option}` option writes to `OUT_DIR`, which Cargo only sets for packages with a build script. Add a `build.rs`, even one with an empty `fn main() {{}}`"),
+        ));
+    };
+    let directory = out_dir.join("discrimin-ant");
+    let path = directory.join(file_name);
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if existing == contents {
+            return Ok(());
+        }
+        let name = item_ident.unraw().to_string();
+        let existing_header = existing.lines().next().unwrap_or_default();
+        if existing_header != contents.lines().next().unwrap_or_default()
+            && let Some(existing_source) = header_source(existing_header).filter(|source| declares_enum(source, &name))
+        {
+            return Err(syn::Error::new_spanned(
+                item_ident,
+                format!("`{file_name}` is already exported by the enum `{name}` in `{existing_source}`. Give one of them its own file name with `export_name = \"..\"`"),
+            ));
+        }
+    }
+    std::fs::create_dir_all(&directory)
+        .and_then(|()| std::fs::write(&path, contents))
+        .map_err(|error| syn::Error::new(Span::call_site(), format!("Could not write `{}`: {error}", path.display())))
+}
+
+/// Returns the source file named by the first line of an export, e.g. `src/net.rs` from
+/// ``// Generated by discrimin-ant from the Rust enum `Message` in `src/net.rs`. Do not edit.``
+///
+/// * `header`: The first line of the export.
+fn header_source(header: &str) -> Option<&str> {
+    let (_, rest) = header.split_once("` in `")?;
+    rest.split_once('`').map(|(source, _)| source)
+}
+
+/// Whether a source file, relative to the manifest of the crate being compiled, still declares an
+/// enum with the given name.
+///
+/// * `source`: The source file.
+/// * `name`: The name of the enum, without any `r#` prefix.
+fn declares_enum(source: &str, name: &str) -> bool {
+    let path = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from(source), |dir| PathBuf::from(dir).join(source));
+    std::fs::read_to_string(path).is_ok_and(|contents| contents.contains(&format!("enum {name}")) || contents.contains(&format!("enum r#{name}")))
+}
@@ -0,0 +1,107 @@
+//! Renders an enum as a C and C++ header, e.g. for `#[discriminant(u8, c_header)]`.
+//!
+//! C++ gets an `enum class` with the repr as its underlying type, while C gets a `typedef enum`
+//! along with a `typedef` of the repr to store it in, since C enums cannot choose their width.
+//! Before C23, C only allows enum values in the range of `int`, so larger ones rely on a compiler
+//! extension. C++ names the repr from `<cstdint>` as `std::uint8_t` and so on, since only the
+//! `std` names are guaranteed to be declared there.
+use std::fmt::Write;
+
+use super::super::analysis::FieldShape;
+use super::super::case::Case;
+use super::super::value::DiscriminantValue;
+use super::ExportEnum;
+
+/// Renders the header for an enum.
+///
+/// * `export`: The enum to render.
+///
+/// # Errors
+/// Fails if the repr has no C equivalent, such as `u128`.
+pub fn render_c_header(export: &ExportEnum) -> Result<String, String> {
+    let name = &export.name;
+    let c_type = c_type(&export.repr).ok_or_else(|| format!("`{}` has no C equivalent. Use a repr of at most 64 bits", export.repr))?;
+    let cpp_type = format!("std::{c_type}");
+    let guard = format!("DISCRIMIN_ANT_{}_H", Case::ScreamingSnake.apply(name));
+    let literals = export.variants.iter().map(|variant| c_literal(variant.value, &export.repr)).collect::<Vec<_>>();
+
+    let mut header = String::new();
+    let _ = writeln!(header, "/* Generated by discrimin-ant from the Rust enum {}. Do not edit. */", export.origin());
+    let _ = writeln!(header, "#ifndef {guard}\n#define {guard}\n");
+
+    let _ = writeln!(header, "#ifdef __cplusplus\n#include <cstdint>\n");
+    let _ = writeln!(header, "enum class {name} : {cpp_type} {{");
+    for (variant, literal) in export.variants.iter().zip(&literals) {
+        let _ = writeln!(header, "    /* Rust: {} */", rust_variant(&variant.name, &variant.fields));
+        let _ = writeln!(header, "    {} = {literal},", variant.name);
+    }
+    let _ = writeln!(header, "}};\n");
+    let _ = writeln!(header, "static_assert(sizeof({name}) == sizeof({cpp_type}), \"{name} must have the size of {cpp_type}\");");
+    for (variant, literal) in export.variants.iter().zip(&literals) {
+        let _ = writeln!(header, "static_assert(static_cast<{cpp_type}>({name}::{}) == {literal}, \"{name}::{} must be {}\");", variant.name, variant.name, variant.value);
+    }
+
+    let _ = writeln!(header, "#else\n#include <stdint.h>\n");
+    let _ = writeln!(header, "/* The type that holds a {name} on the wire. */\ntypedef {c_type} {name}_repr;\n");
+    let _ = writeln!(header, "typedef enum {name} {{");
+    for (variant, literal) in export.variants.iter().zip(&literals) {
+        let _ = writeln!(header, "    /* Rust: {} */", rust_variant(&variant.name, &variant.fields));
+        let _ = writeln!(header, "    {name}_{} = {literal},", variant.name);
+    }
+    let _ = writeln!(header, "}} {name};\n");
+    for (variant, literal) in export.variants.iter().zip(&literals) {
+        let _ = writeln!(header, "_Static_assert({name}_{} == {literal}, \"{name}_{} must be {}\");", variant.name, variant.name, variant.value);
+    }
+    let _ = writeln!(header, "#endif\n\n#endif /* {guard} */");
+    Ok(header)
+}
+
+/// Returns the C type for a repr, or `None` if it has no equivalent.
+///
+/// * `repr`: The repr, e.g. `u8`.
+fn c_type(repr: &str) -> Option<&'static str> {
+    match repr {
+        "u8" => Some("uint8_t"),
+        "u16" => Some("uint16_t"),
+        "u32" => Some("uint32_t"),
+        "u64" => Some("uint64_t"),
+        "usize" => Some("uintptr_t"),
+        "i8" => Some("int8_t"),
+        "i16" => Some("int16_t"),
+        "i32" => Some("int32_t"),
+        "i64" => Some("int64_t"),
+        "isize" => Some("intptr_t"),
+        _ => None,
+    }
+}
+
+/// Writes a discriminant as a C integer literal with a suffix wide enough for the repr, e.g. `1u`
+/// or `-5ll`. The smallest value of a signed repr is written as an expression since its magnitude
+/// does not fit in a literal.
+///
+/// * `value`: The discriminant.
+/// * `repr`: The repr, e.g. `u8`.
+fn c_literal(value: DiscriminantValue, repr: &str) -> String {
+    let wide = matches!(repr, "u64" | "i64" | "usize" | "isize");
+    match value {
+        DiscriminantValue::Unsigned(value) => format!("{value}{}", if wide { "ull" } else { "u" }),
+        DiscriminantValue::Signed(value) => {
+            let suffix = if wide { "ll" } else { "" };
+            let min = match repr {
+                "i8" => i128::from(i8::MIN),
+                "i16" => i128::from(i16::MIN),
+                "i32" => i128::from(i32::MIN),
+                _ => i128::from(i64::MIN),
+            };
+            if value == min { format!("({}{suffix} - 1)", value.saturating_add(1)) } else { format!("{value}{suffix}") }
+        }
+    }
+}
+
+/// Writes a variant as it appears in Rust, e.g. `Move { x: i32, y: i32 }`.
+///
+/// * `name`: The name of the variant.
+/// * `fields`: The fields of the variant.
+fn rust_variant(name: &str, fields: &FieldShape) -> String {
+    format!("{name}{}", fields.to_rust())
+}
@@ -0,0 +1,69 @@
+//! Renders an enum as a protobuf `enum`, e.g. for `#[discriminant(u8, proto)]`.
+//!
+//! Following the protobuf style guide, each value is named in `SCREAMING_SNAKE_CASE` and prefixed
+//! with the name of the enum, so `Message::Ping` becomes `MESSAGE_PING`. The value with the
+//! discriminant `0` is written first, as proto3 requires.
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::super::case::Case;
+use super::super::value::DiscriminantValue;
+use super::{ExportEnum, ExportVariant};
+
+/// Renders the `.proto` file for an enum.
+///
+/// * `export`: The enum to render.
+/// * `package`: The protobuf package of the file, if any.
+///
+/// # Errors
+/// Fails if protobuf cannot represent the enum: a discriminant does not fit in an `int32`, no
+/// variant has the discriminant `0`, or two variants have the same protobuf name.
+pub fn render_proto(export: &ExportEnum, package: Option<&str>) -> Result<String, String> {
+    let name = &export.name;
+    if let Some(variant) = export.variants.iter().find(|variant| i32::try_from(as_i128(variant.value)).is_err()) {
+        return Err(format!("`{}` has the discriminant `{}`, which does not fit in the `int32` that protobuf uses for enums", variant.name, variant.value));
+    }
+    let Some(zero) = export.variants.iter().find(|variant| as_i128(variant.value) == 0) else {
+        return Err(format!("proto3 requires `{name}` to have a variant with the discriminant `0`. Add one, such as `Unspecified = 0`"));
+    };
+
+    let prefix = Case::ScreamingSnake.apply(name);
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for variant in &export.variants {
+        if let Some(first) = seen.insert(value_name(&prefix, variant), &variant.name) {
+            return Err(format!("`{}` and `{first}` both produce the protobuf value `{}`", variant.name, value_name(&prefix, variant)));
+        }
+    }
+
+    let mut proto = String::new();
+    let _ = writeln!(proto, "// Generated by discrimin-ant from the Rust enum {}. Do not edit.", export.origin());
+    let _ = writeln!(proto, "syntax = \"proto3\";\n");
+    if let Some(package) = package {
+        let _ = writeln!(proto, "package {package};\n");
+    }
+    let _ = writeln!(proto, "enum {name} {{");
+    for variant in std::iter::once(zero).chain(export.variants.iter().filter(|variant| !std::ptr::eq(*variant, zero))) {
+        let _ = writeln!(proto, "  // Rust: {}{}", variant.name, variant.fields.to_rust());
+        let _ = writeln!(proto, "  {} = {};", value_name(&prefix, variant), variant.value);
+    }
+    let _ = writeln!(proto, "}}");
+    Ok(proto)
+}
+
+/// Creates the protobuf name of a value, e.g. `MESSAGE_PING`.
+///
+/// * `prefix`: The name of the enum in `SCREAMING_SNAKE_CASE`.
+/// * `variant`: The variant.
+fn value_name(prefix: &str, variant: &ExportVariant) -> String {
+    format!("{prefix}_{}", Case::ScreamingSnake.apply(&variant.name))
+}
+
+/// Converts a discriminant to an `i128`, saturating values of `u128` that do not fit.
+///
+/// * `value`: The discriminant.
+fn as_i128(value: DiscriminantValue) -> i128 {
+    match value {
+        DiscriminantValue::Signed(value) => value,
+        DiscriminantValue::Unsigned(value) => i128::try_from(value).unwrap_or(i128::MAX),
+    }
+}
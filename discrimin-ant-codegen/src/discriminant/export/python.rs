@@ -0,0 +1,34 @@
+//! Renders an enum as a Python `enum.IntEnum`, e.g. for `#[discriminant(u8, python)]`.
+use std::fmt::Write;
+
+use super::ExportEnum;
+
+/// The Python keywords, as listed by `keyword.kwlist`. Rust can name variants after all of them,
+/// with raw identifiers such as `r#if` where they are Rust keywords too.
+const PYTHON_KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or",
+    "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Renders the Python module for an enum.
+///
+/// * `export`: The enum to render.
+///
+/// # Errors
+/// Fails if a variant is named after a Python keyword, such as `None`.
+pub fn render_python(export: &ExportEnum) -> Result<String, String> {
+    let name = &export.name;
+    if let Some(variant) = export.variants.iter().find(|variant| PYTHON_KEYWORDS.contains(&variant.name.as_str())) {
+        return Err(format!("`{}` is a keyword in Python, so it cannot name a member of `{name}`", variant.name));
+    }
+
+    let mut python = String::new();
+    let _ = writeln!(python, "# Generated by discrimin-ant from the Rust enum {}. Do not edit.", export.origin());
+    let _ = writeln!(python, "from enum import IntEnum\n\n");
+    let _ = writeln!(python, "class {name}(IntEnum):");
+    for variant in &export.variants {
+        let _ = writeln!(python, "    # Rust: {}{}", variant.name, variant.fields.to_rust());
+        let _ = writeln!(python, "    {} = {}", variant.name, variant.value);
+    }
+    Ok(python)
+}
@@ -0,0 +1,41 @@
+//! Renders an enum as a TypeScript `const enum`, e.g. for `#[discriminant(u8, typescript)]`.
+use std::fmt::Write;
+
+use super::super::value::DiscriminantValue;
+use super::ExportEnum;
+
+/// The largest integer that a TypeScript `number` holds exactly, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: u128 = (1 << 53) - 1;
+
+/// Renders the TypeScript module for an enum.
+///
+/// * `export`: The enum to render.
+///
+/// # Errors
+/// Fails if a discriminant is too large for a TypeScript `number` to hold exactly.
+pub fn render_typescript(export: &ExportEnum) -> Result<String, String> {
+    let name = &export.name;
+    if let Some(variant) = export.variants.iter().find(|variant| magnitude(variant.value) > MAX_SAFE_INTEGER) {
+        return Err(format!("`{}` has the discriminant `{}`, which a TypeScript `number` cannot hold exactly", variant.name, variant.value));
+    }
+
+    let mut typescript = String::new();
+    let _ = writeln!(typescript, "// Generated by discrimin-ant from the Rust enum {}. Do not edit.\n", export.origin());
+    let _ = writeln!(typescript, "export const enum {name} {{");
+    for variant in &export.variants {
+        let _ = writeln!(typescript, "  /** Rust: {}{} */", variant.name, variant.fields.to_rust());
+        let _ = writeln!(typescript, "  {} = {},", variant.name, variant.value);
+    }
+    let _ = writeln!(typescript, "}}");
+    Ok(typescript)
+}
+
+/// Returns the magnitude of a discriminant.
+///
+/// * `value`: The discriminant.
+const fn magnitude(value: DiscriminantValue) -> u128 {
+    match value {
+        DiscriminantValue::Signed(value) => value.unsigned_abs(),
+        DiscriminantValue::Unsigned(value) => value,
+    }
+}
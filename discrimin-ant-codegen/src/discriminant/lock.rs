@@ -0,0 +1,190 @@
+//! Checks the discriminants of an enum against a checked-in lock file, e.g.
+//! `lock = "discriminants.lock"`, so that variants cannot be renumbered by accident.
+//!
+//! The lock file has a section for each enum, listing the discriminant of each variant:
+//! ```text
+//! [Message]
+//! Ping = 1
+//! Data = 2
+//! ```
+//! Blank lines and lines starting with `#` are ignored.
+//!
+//! Sections are named after the enum alone, so two enums with the same name in different modules
+//! must be given their own sections with `lock(path = "..", section = "..")`.
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Expr, Ident, LitStr};
+
+use super::DiscriminantVariant;
+use super::options::LockOptions;
+use super::value::{self, DiscriminantValue};
+
+/// A parsed lock file, mapping the name of each enum to the discriminants of its variants.
+pub struct LockFile {
+    /// The locked discriminant of each variant, in file order, by enum name.
+    pub sections: HashMap<String, Vec<(String, Expr)>>,
+}
+
+impl LockFile {
+    /// Parses the contents of a lock file.
+    ///
+    /// * `contents`: The contents of the lock file.
+    ///
+    /// # Errors
+    /// Fails with a description of the first invalid line.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut sections: HashMap<String, Vec<(String, Expr)>> = HashMap::new();
+        let mut current: Option<String> = None;
+        for (i, line) in contents.lines().enumerate() {
+            let line_number = i.saturating_add(1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                let name = name.trim().to_string();
+                if sections.insert(name.clone(), Vec::new()).is_some() {
+                    return Err(format!("line {line_number} repeats the section `[{name}]`"));
+                }
+                current = Some(name);
+                continue;
+            }
+            let Some((variant, discriminant)) = line.split_once('=') else {
+                return Err(format!("line {line_number} should be a section like `[Message]` or a variant like `Ping = 1`"));
+            };
+            let Some(section) = current.as_ref().and_then(|name| sections.get_mut(name)) else {
+                return Err(format!("line {line_number} is not in a section. Add a section like `[Message]` above it"));
+            };
+            let discriminant = syn::parse_str(discriminant.trim()).map_err(|_| format!("line {line_number} has an invalid discriminant `{}`", discriminant.trim()))?;
+            section.push((variant.trim().to_string(), discriminant));
+        }
+        Ok(Self { sections })
+    }
+}
+
+/// Reads the lock file, relative to the manifest of the crate being compiled, and checks the
+/// discriminants of the enum against its section.
+///
+/// Returns code that makes the compiler track the lock file, so that changing it recompiles the
+/// enum, along with `const` assertions for discriminants that cannot be evaluated at macro time.
+///
+/// * `options`: The path to the lock file and the section for the enum.
+/// * `discriminants`: The discriminants of the current variants.
+/// * `retired`: The discriminants of the retired variants.
+/// * `item_ident`: The name of the enum.
+/// * `repr`: The repr for the enum.
+///
+/// # Errors
+/// Fails if the lock file cannot be read or the discriminants do not match it.
+pub fn check_lock_file(options: &LockOptions, discriminants: &[DiscriminantVariant], retired: &[DiscriminantVariant], item_ident: &Ident, repr: &Ident) -> syn::Result<TokenStream> {
+    let path = &options.path;
+    let section = options.section.as_ref().map_or_else(|| item_ident.to_string(), LitStr::value);
+    let full_path = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from(path.value()), |dir| PathBuf::from(dir).join(path.value()));
+    let contents = std::fs::read_to_string(&full_path).map_err(|error| {
+        syn::Error::new(
+            path.span(),
+            format!("Could not read the lock file `{}`: {error}. Create it with:\n\n{}", path.value(), generate_section(discriminants, retired, &section)),
+        )
+    })?;
+    let lock = LockFile::parse(&contents).map_err(|error| syn::Error::new(path.span(), format!("Invalid lock file `{}`: {error}", path.value())))?;
+    let Some(locked) = lock.sections.get(&section) else {
+        return Err(syn::Error::new(
+            path.span(),
+            format!("`[{section}]` is not in the lock file `{}`. Add:\n\n{}", path.value(), generate_section(discriminants, retired, &section)),
+        ));
+    };
+    let assertions = check_locked_discriminants(locked, discriminants, retired, item_ident, repr, &path.value(), &section)?;
+
+    let full_path = full_path.to_string_lossy();
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#full_path);
+        #assertions
+    })
+}
+
+/// Checks the discriminants of the enum against its section of the lock file.
+///
+/// * `locked`: The locked discriminant of each variant.
+/// * `discriminants`: The discriminants of the current variants.
+/// * `retired`: The discriminants of the retired variants.
+/// * `item_ident`: The name of the enum.
+/// * `repr`: The repr for the enum.
+/// * `path`: The path to the lock file, used for errors.
+/// * `section`: The name of the enum's section of the lock file, used for errors.
+///
+/// # Errors
+/// Fails if a locked discriminant changed, a locked variant was removed without being retired, or
+/// a variant is not locked.
+pub fn check_locked_discriminants(locked: &[(String, Expr)], discriminants: &[DiscriminantVariant], retired: &[DiscriminantVariant], item_ident: &Ident, repr: &Ident, path: &str, section: &str) -> syn::Result<TokenStream> {
+    let mut errors: Vec<syn::Error> = Vec::new();
+    let mut assertions = quote! {};
+    let variants = discriminants.iter().chain(retired).map(|discriminant| (discriminant.variant.ident.to_string(), discriminant)).collect::<HashMap<_, _>>();
+
+    for (name, locked_expr) in locked {
+        let Some(DiscriminantVariant { variant, expr, value, .. }) = variants.get(name) else {
+            errors.push(syn::Error::new_spanned(
+                item_ident,
+                format!("`{name}` is locked in `{path}` but no longer exists. Keep the variant and mark it `#[discriminant(retired)]` so that its discriminant is not reused"),
+            ));
+            continue;
+        };
+        let locked_value = value::fold_for_repr(locked_expr, repr).map_err(|error| syn::Error::new_spanned(&variant.ident, format!("The locked discriminant of `{name}` in `{path}` is invalid: {error}")))?;
+        match (value, locked_value) {
+            (Some(value), Some(locked_value)) if *value != locked_value => {
+                errors.push(syn::Error::new_spanned(
+                    &variant.ident,
+                    format!("`{name}` is locked to the discriminant `{locked_value}` in `{path}`, but it is now `{value}`. Give it `= {locked_value}` to restore it"),
+                ));
+            }
+            (Some(_), Some(_)) => {}
+            _ => {
+                let message = format!("`{name}` of `{item_ident}` does not have its locked discriminant `{}` from `{path}`", quote!(#locked_expr));
+                let span = variant.ident.span();
+                assertions = quote_spanned! {span=>
+                    #assertions
+                    const _: () = {
+                        let value: #repr = #expr;
+                        let locked: #repr = #locked_expr;
+                        assert!(value == locked, #message);
+                    };
+                };
+            }
+        }
+    }
+
+    for DiscriminantVariant { variant, expr, value, .. } in discriminants.iter().chain(retired) {
+        if locked.iter().all(|(name, _)| variant.ident != name) {
+            let discriminant = value.map_or_else(|| quote!(#expr).to_string(), |value: DiscriminantValue| value.to_string());
+            errors.push(syn::Error::new_spanned(
+                &variant.ident,
+                format!(
+                    "`{}` is not in the lock file `{path}`. Add `{} = {discriminant}` to the `[{section}]` section to lock its discriminant",
+                    variant.ident, variant.ident
+                ),
+            ));
+        }
+    }
+
+    let mut errors = errors.into_iter();
+    if let Some(mut error) = errors.next() {
+        error.extend(errors);
+        return Err(error);
+    }
+    Ok(assertions)
+}
+
+/// Generates the section of the lock file for the enum, used to tell the user how to create it.
+///
+/// * `discriminants`: The discriminants of the current variants.
+/// * `retired`: The discriminants of the retired variants.
+/// * `section`: The name of the section.
+fn generate_section(discriminants: &[DiscriminantVariant], retired: &[DiscriminantVariant], section: &str) -> String {
+    let lines = discriminants.iter().chain(retired).map(|DiscriminantVariant { variant, expr, value, .. }| {
+        let discriminant = value.map_or_else(|| quote!(#expr).to_string(), |value| value.to_string());
+        format!("{} = {discriminant}", variant.ident)
+    });
+    std::iter::once(format!("[{section}]")).chain(lines).collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,440 @@
+//! Parsing for the arguments given to the `discriminant` attribute.
+use std::path::Path as FsPath;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Ident, LitStr, Path, Token, Type, Visibility};
+
+use super::case::Case;
+
+/// The options passed to the `discriminant` attribute, e.g. `#[discriminant(u8, serde)]`.
+#[derive(Default)]
+pub struct DiscriminantOptions {
+    /// The `repr` for the enum, given either as the first argument or as `repr = u8`. Otherwise,
+    /// the `repr` of the enum is used.
+    pub repr: Option<Ident>,
+    /// The options for generating `serde::Serialize` and `serde::Deserialize`, if requested.
+    pub serde: Option<SerdeOptions>,
+    /// Whether to generate `schemars::JsonSchema` describing the `serde` encoding, requested with
+    /// `schemars`.
+    pub schemars: bool,
+    /// Whether to generate `discrimin_ant::Encode` and `discrimin_ant::Decode`, requested with
+    /// `codec`.
+    pub codec: bool,
+    /// The options for the fieldless enum.
+    pub fieldless: FieldlessOptions,
+    /// The case to convert the name of each variant to, e.g. `rename_all = "snake_case"`.
+    pub rename_all: Option<Case>,
+    /// The name of a catch-all variant added to the fieldless enum to hold unknown discriminants,
+    /// e.g. `other = Unknown`.
+    pub other: Option<Ident>,
+    /// The options for the open newtype, if requested with `code`.
+    pub code: Option<CodeOptions>,
+    /// Whether every variant must have an explicit discriminant, set with `explicit` or `implicit`.
+    /// Otherwise, see [`DiscriminantOptions::explicit`].
+    pub explicit: Option<bool>,
+    /// Discriminants that no variant may use, given as integers or ranges, e.g.
+    /// `reserved(3, 7, 10..=19)`.
+    pub reserved: Vec<Expr>,
+    /// The lock file that the discriminants must match, e.g. `lock = "discriminants.lock"`.
+    pub lock: Option<LockOptions>,
+    /// The options for `SCHEMA_HASH`.
+    pub schema_hash: SchemaHashOptions,
+    /// The files to export the enum to, written to `OUT_DIR`.
+    pub export: ExportOptions,
+}
+
+/// The options for the fieldless enum, e.g. `fieldless(name = MsgKind, vis = pub(crate), derive(Clone))`.
+#[derive(Default)]
+pub struct FieldlessOptions {
+    /// The name of the fieldless enum. Defaults to the name of the original enum followed by `_`.
+    pub name: Option<Ident>,
+    /// The visibility of the fieldless enum. Defaults to the visibility of the original enum.
+    pub vis: Option<Visibility>,
+    /// The traits to derive for the fieldless enum. Defaults to `Clone`, `Copy`, `Debug`,
+    /// `PartialEq`, `Eq` and `Hash`.
+    pub derives: Option<Vec<Path>>,
+    /// Whether to skip generating the fieldless enum entirely.
+    pub skip: bool,
+    /// The error returned by `TryFrom` when a value is not a discriminant. Must implement `From`
+    /// for the repr. Defaults to `discrimin_ant::UnknownDiscriminant`.
+    pub error: Option<Type>,
+    /// Whether `TryFrom` returns `discrimin_ant::DiscriminantError`, which tells retired and
+    /// reserved discriminants apart from unknown ones.
+    pub retired_error: bool,
+}
+
+/// The options for a single variant, given with a `#[discriminant(..)]` attribute on the variant,
+/// e.g. `#[discriminant(rename = "ping")]`.
+#[derive(Default)]
+pub struct VariantOptions {
+    /// The name of the variant, overriding `rename_all`.
+    pub rename: Option<LitStr>,
+    /// Whether the variant has been retired. It is removed from the enum, but its discriminant can
+    /// never be used again.
+    pub retired: bool,
+}
+
+/// The options for the open newtype, e.g. `code(name = MessageCode)`.
+#[derive(Default)]
+pub struct CodeOptions {
+    /// The name of the newtype. Defaults to the name of the original enum followed by `Code`.
+    pub name: Option<Ident>,
+}
+
+/// The options for checking the discriminants against a lock file, given either as
+/// `lock = "discriminants.lock"` or as `lock(path = "discriminants.lock", section = "net::Message")`.
+pub struct LockOptions {
+    /// The path to the lock file, relative to the manifest of the crate.
+    pub path: LitStr,
+    /// The section of the lock file for the enum. Defaults to the name of the enum.
+    pub section: Option<LitStr>,
+}
+
+/// The files to export the enum to, each requested with its own option, e.g. `c_header` or
+/// `proto(package = "chat.v1")`.
+#[derive(Default)]
+pub struct ExportOptions {
+    /// The name of the exported files without their extension, e.g. `export_name = "net_message"`.
+    /// Defaults to the name of the enum, or its `snake_case` form for Python.
+    pub name: Option<LitStr>,
+    /// Whether to write a C and C++ header, requested with `c_header`.
+    pub c_header: bool,
+    /// The options for the `.proto` file, if requested with `proto`.
+    pub proto: Option<ProtoOptions>,
+    /// Whether to write a TypeScript `const enum`, requested with `typescript`.
+    pub typescript: bool,
+    /// Whether to write a Python `enum.IntEnum`, requested with `python`.
+    pub python: bool,
+}
+
+/// The options for the `.proto` file, e.g. `proto(package = "chat.v1")`.
+#[derive(Default)]
+pub struct ProtoOptions {
+    /// The protobuf package of the file.
+    pub package: Option<LitStr>,
+}
+
+/// The options for `SCHEMA_HASH`, e.g. `schema_hash(fields)`.
+#[derive(Default)]
+pub struct SchemaHashOptions {
+    /// Whether the names and types of the fields of each variant are part of the hash.
+    pub fields: bool,
+}
+
+/// The options for generating `serde` implementations, e.g. `serde(human_readable)`.
+#[derive(Default)]
+pub struct SerdeOptions {
+    /// Whether the fieldless enum should be written as its variant name in human-readable formats.
+    pub human_readable: bool,
+}
+
+impl DiscriminantOptions {
+    /// Parses the options from the arguments of the attribute.
+    ///
+    /// * `attr`: The tokens inside the `discriminant` attribute.
+    pub fn parse(attr: TokenStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+        syn::meta::parser(|meta| options.parse_meta(&meta)).parse2(attr)?;
+        Ok(options)
+    }
+
+    /// Parses the options from every `#[discriminant(..)]` helper attribute in `attrs`.
+    ///
+    /// * `attrs`: The attributes of the enum.
+    pub fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("discriminant")) {
+            attr.parse_nested_meta(|meta| options.parse_meta(&meta))?;
+        }
+        Ok(options)
+    }
+
+    /// Whether every variant must have an explicit discriminant.
+    ///
+    /// Unless `explicit` or `implicit` is given, this is on when the `DISCRIMIN_ANT_EXPLICIT`
+    /// environment variable is `1` or `true` while compiling a local package, e.g. by setting it in
+    /// the `[env]` table of `.cargo/config.toml`. See [`explicit_from_env`].
+    pub fn explicit(&self) -> bool {
+        self.explicit.unwrap_or_else(|| {
+            let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR");
+            explicit_from_env(manifest_dir.as_deref().map(FsPath::new), std::env::var("DISCRIMIN_ANT_EXPLICIT").ok().as_deref())
+        })
+    }
+
+    /// Code that makes Cargo recompile the enum when `DISCRIMIN_ANT_EXPLICIT` changes, if
+    /// [`DiscriminantOptions::explicit`] depends on it.
+    ///
+    /// Proc macros cannot declare the environment variables they read on stable Rust, so the
+    /// variable is read again with `option_env!`, which the compiler does track.
+    pub fn track_explicit_env(&self) -> TokenStream {
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR");
+        if self.explicit.is_some() || !manifest_dir.as_deref().map(FsPath::new).is_some_and(is_local_package) {
+            return quote! {};
+        }
+        quote! {
+            const _: Option<&str> = option_env!("DISCRIMIN_ANT_EXPLICIT");
+        }
+    }
+
+    /// Parses a single option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("repr") {
+            self.repr = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if meta.path.is_ident("serde") {
+            let serde = self.serde.get_or_insert_default();
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| serde.parse_meta(&nested))?;
+            }
+            return Ok(());
+        }
+        if meta.path.is_ident("schemars") {
+            self.schemars = true;
+            return Ok(());
+        }
+        if meta.path.is_ident("codec") {
+            self.codec = true;
+            return Ok(());
+        }
+        if meta.path.is_ident("fieldless") {
+            return meta.parse_nested_meta(|nested| self.fieldless.parse_meta(&nested));
+        }
+        if meta.path.is_ident("code") {
+            let code = self.code.get_or_insert_default();
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| code.parse_meta(&nested))?;
+            }
+            return Ok(());
+        }
+        if meta.path.is_ident("explicit") {
+            self.explicit = Some(true);
+            return Ok(());
+        }
+        if meta.path.is_ident("implicit") {
+            self.explicit = Some(false);
+            return Ok(());
+        }
+        if meta.path.is_ident("reserved") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            self.reserved.extend(Punctuated::<Expr, Token![,]>::parse_terminated(&content)?);
+            return Ok(());
+        }
+        if meta.path.is_ident("lock") {
+            self.lock = Some(LockOptions::parse(meta)?);
+            return Ok(());
+        }
+        if meta.path.is_ident("c_header") {
+            self.export.c_header = true;
+            return Ok(());
+        }
+        if meta.path.is_ident("typescript") {
+            self.export.typescript = true;
+            return Ok(());
+        }
+        if meta.path.is_ident("export_name") {
+            self.export.name = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if meta.path.is_ident("python") {
+            self.export.python = true;
+            return Ok(());
+        }
+        if meta.path.is_ident("proto") {
+            let proto = self.export.proto.get_or_insert_default();
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| proto.parse_meta(&nested))?;
+            }
+            return Ok(());
+        }
+        if meta.path.is_ident("schema_hash") {
+            return meta.parse_nested_meta(|nested| self.schema_hash.parse_meta(&nested));
+        }
+        if meta.path.is_ident("other") {
+            self.other = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if meta.path.is_ident("rename_all") {
+            self.rename_all = Some(Case::parse(&meta.value()?.parse()?)?);
+            return Ok(());
+        }
+        if self.repr.is_none()
+            && let Some(ident) = meta.path.get_ident()
+        {
+            self.repr = Some(ident.clone());
+            return Ok(());
+        }
+        Err(meta.error("Unsupported `discriminant` option"))
+    }
+}
+
+impl VariantOptions {
+    /// Parses the options from every `#[discriminant(..)]` attribute on a variant.
+    ///
+    /// * `attrs`: The attributes of the variant.
+    pub fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("discriminant")) {
+            attr.parse_nested_meta(|meta| options.parse_meta(&meta))?;
+        }
+        Ok(options)
+    }
+
+    /// Parses a single variant option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("rename") {
+            self.rename = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if meta.path.is_ident("retired") {
+            self.retired = true;
+            return Ok(());
+        }
+        Err(meta.error("Unsupported variant option"))
+    }
+}
+
+impl CodeOptions {
+    /// Parses a single `code` option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        Err(meta.error("Unsupported `code` option"))
+    }
+}
+
+impl LockOptions {
+    /// Parses the `lock` option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        if !meta.input.peek(syn::token::Paren) {
+            return Ok(Self { path: meta.value()?.parse()?, section: None });
+        }
+        let (mut path, mut section) = (None, None);
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident("path") {
+                path = Some(nested.value()?.parse()?);
+                return Ok(());
+            }
+            if nested.path.is_ident("section") {
+                section = Some(nested.value()?.parse()?);
+                return Ok(());
+            }
+            Err(nested.error("Unsupported `lock` option"))
+        })?;
+        let path = path.ok_or_else(|| meta.error("The `lock` option requires a `path`, e.g. `lock(path = \"discriminants.lock\")`"))?;
+        Ok(Self { path, section })
+    }
+}
+
+impl ProtoOptions {
+    /// Parses a single `proto` option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("package") {
+            self.package = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        Err(meta.error("Unsupported `proto` option"))
+    }
+}
+
+impl SchemaHashOptions {
+    /// Parses a single `schema_hash` option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("fields") {
+            self.fields = true;
+            return Ok(());
+        }
+        Err(meta.error("Unsupported `schema_hash` option"))
+    }
+}
+
+impl SerdeOptions {
+    /// Parses a single `serde` option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("human_readable") {
+            self.human_readable = true;
+            return Ok(());
+        }
+        Err(meta.error("Unsupported `serde` option"))
+    }
+}
+
+impl FieldlessOptions {
+    /// Parses a single `fieldless` option.
+    ///
+    /// * `meta`: The option to parse.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if meta.path.is_ident("vis") {
+            self.vis = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if meta.path.is_ident("derive") {
+            let derives = self.derives.get_or_insert_default();
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|derive| {
+                    derives.push(derive.path);
+                    Ok(())
+                })?;
+            }
+            return Ok(());
+        }
+        if meta.path.is_ident("skip") {
+            self.skip = true;
+            return Ok(());
+        }
+        if meta.path.is_ident("error") {
+            self.error = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if meta.path.is_ident("retired_error") {
+            self.retired_error = true;
+            return Ok(());
+        }
+        Err(meta.error("Unsupported `fieldless` option"))
+    }
+}
+
+/// Whether `DISCRIMIN_ANT_EXPLICIT` turns on `explicit` for the package being compiled.
+///
+/// It is on when the variable is `1` or `true` and the package is local, i.e. a member of the
+/// workspace or a path dependency. Packages downloaded from a registry or git repository are not
+/// affected, since their authors cannot add the missing discriminants.
+///
+/// * `manifest_dir`: The directory of the package's `Cargo.toml`, if compiled by Cargo.
+/// * `value`: The value of `DISCRIMIN_ANT_EXPLICIT`, if set.
+pub fn explicit_from_env(manifest_dir: Option<&FsPath>, value: Option<&str>) -> bool {
+    manifest_dir.is_some_and(is_local_package) && value.is_some_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+/// Whether a package is local rather than downloaded by Cargo, judging by where its sources are.
+///
+/// * `manifest_dir`: The directory of the package's `Cargo.toml`.
+fn is_local_package(manifest_dir: &FsPath) -> bool {
+    let components: Vec<_> = manifest_dir.iter().collect();
+    !components.windows(2).any(|pair| pair == ["registry", "src"] || pair == ["git", "checkouts"])
+}
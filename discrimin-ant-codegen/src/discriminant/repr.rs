@@ -0,0 +1,112 @@
+//! Resolves the `repr` of an enum from the `discriminant` attribute and any `repr` attributes
+//! already on the enum.
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Ident, Meta, Token};
+
+/// The primitive representations supported for discriminants.
+/// See <https://doc.rust-lang.org/reference/type-layout.html#primitive-representations>
+pub const PRIMITIVE_REPRS: [&str; 12] = ["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+
+/// The resolved `repr` of an enum.
+pub struct Repr {
+    /// The primitive integer type of the discriminant.
+    pub primitive: Ident,
+    /// Any other representations requested for the enum, such as `C`.
+    pub others: Vec<Meta>,
+}
+
+impl Repr {
+    /// Resolves the `repr` for an enum, removing any existing `repr` attributes from it so that
+    /// they can be combined into one.
+    ///
+    /// * `requested`: The repr given to the `discriminant` attribute, if any.
+    /// * `attrs`: The attributes of the enum.
+    ///
+    /// # Errors
+    /// Fails if the requested repr conflicts with an existing one or no repr is given at all.
+    pub fn resolve(requested: Option<&Ident>, attrs: &mut Vec<Attribute>) -> syn::Result<Self> {
+        let (existing, others) = Self::take_from_attrs(attrs)?;
+        let primitive = match (requested, existing) {
+            (Some(requested), Some(existing)) if *requested != existing => {
+                return Err(syn::Error::new(
+                    requested.span(),
+                    format!("The discriminant type `{requested}` conflicts with the existing `#[repr({existing})]`"),
+                ));
+            }
+            (Some(requested), _) => requested.clone(),
+            (None, Some(existing)) => existing,
+            (None, None) => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "Missing discriminant type. Expected something like `#[discriminant(u8)]` or an existing `#[repr(u8)]`",
+                ));
+            }
+        };
+        Ok(Self { primitive, others })
+    }
+
+    /// Resolves the `repr` for an enum that cannot be modified, such as in a derive. The enum must
+    /// already have a primitive representation.
+    ///
+    /// * `requested`: The repr given to the `discriminant` helper attribute, if any.
+    /// * `attrs`: The attributes of the enum.
+    ///
+    /// # Errors
+    /// Fails if the enum does not have a primitive representation or it conflicts with the
+    /// requested one.
+    pub fn existing(requested: Option<&Ident>, attrs: &[Attribute]) -> syn::Result<Self> {
+        let (existing, others) = Self::take_from_attrs(&mut attrs.to_vec())?;
+        match (requested, existing) {
+            (Some(requested), Some(existing)) if *requested != existing => Err(syn::Error::new(
+                requested.span(),
+                format!("The discriminant type `{requested}` conflicts with the existing `#[repr({existing})]`"),
+            )),
+            (_, Some(primitive)) => Ok(Self { primitive, others }),
+            (Some(requested), None) => Err(syn::Error::new(
+                requested.span(),
+                format!("`#[derive(Discriminant)]` cannot add a `repr`. Add `#[repr({requested})]` to the enum"),
+            )),
+            (None, None) => Err(syn::Error::new(
+                Span::call_site(),
+                "`#[derive(Discriminant)]` requires a primitive representation such as `#[repr(u8)]` on the enum",
+            )),
+        }
+    }
+
+    /// Removes any `repr` attributes from `attrs`, returning the primitive representation (if any)
+    /// and every other representation.
+    ///
+    /// * `attrs`: The attributes of the enum.
+    ///
+    /// # Errors
+    /// Fails if the attributes contain conflicting primitive representations.
+    fn take_from_attrs(attrs: &mut Vec<Attribute>) -> syn::Result<(Option<Ident>, Vec<Meta>)> {
+        let mut existing: Option<Ident> = None;
+        let mut others = Vec::new();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+            for meta in attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
+                let primitive = match &meta {
+                    Meta::Path(path) => path.get_ident().filter(|ident| PRIMITIVE_REPRS.contains(&ident.to_string().as_str())),
+                    _ => None,
+                };
+                match (primitive, &existing) {
+                    (Some(primitive), Some(existing)) if primitive != existing => {
+                        return Err(syn::Error::new(primitive.span(), format!("Conflicting representations `{existing}` and `{primitive}`")));
+                    }
+                    (Some(primitive), _) => existing = Some(primitive.clone()),
+                    (None, _) => others.push(meta),
+                }
+            }
+        }
+        attrs.retain(|attr| !attr.path().is_ident("repr"));
+        Ok((existing, others))
+    }
+
+    /// Generates the combined `repr` attribute.
+    pub fn to_attribute(&self) -> TokenStream {
+        let Self { primitive, others } = self;
+        quote! { #[repr(#(#others,)* #primitive)] }
+    }
+}
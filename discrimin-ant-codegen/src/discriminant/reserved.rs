@@ -0,0 +1,145 @@
+//! Keeps discriminants that were retired or reserved from being used again, e.g.
+//! `reserved(3, 7, 10..=19)` and `#[discriminant(retired)]`.
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Expr, ExprRange, Ident, RangeLimits};
+
+use super::DiscriminantVariant;
+use super::value::{self, DiscriminantValue};
+
+/// An inclusive range of reserved discriminants.
+pub struct ReservedRange {
+    /// The first reserved discriminant.
+    pub start: DiscriminantValue,
+    /// The last reserved discriminant.
+    pub end: DiscriminantValue,
+}
+
+impl ReservedRange {
+    /// Returns whether `value` is in the range.
+    ///
+    /// * `value`: The discriminant to check.
+    fn contains(&self, value: DiscriminantValue) -> bool {
+        self.start <= value && value <= self.end
+    }
+}
+
+/// Evaluates the reserved discriminants given to the attribute.
+///
+/// * `reserved`: The reserved discriminants, each an integer or a range of integers.
+/// * `repr`: The repr for the enum.
+///
+/// # Errors
+/// Fails if a reserved discriminant is not an integer or a bounded range of them, or does not fit
+/// in the repr.
+pub fn resolve_reserved(reserved: &[Expr], repr: &Ident) -> syn::Result<Vec<ReservedRange>> {
+    reserved
+        .iter()
+        .map(|expr| {
+            let fold = |expr: &Expr| {
+                value::fold_for_repr(expr, repr)?.ok_or_else(|| syn::Error::new_spanned(expr, "Reserved discriminants must be integers or ranges of integers, such as `3` or `10..=19`"))
+            };
+            let Expr::Range(ExprRange { start, limits, end, .. }) = expr else {
+                let value = fold(expr)?;
+                return Ok(ReservedRange { start: value, end: value });
+            };
+            let (Some(start), Some(end)) = (start, end) else {
+                return Err(syn::Error::new_spanned(expr, "Reserved ranges must have a start and an end, such as `10..=19`"));
+            };
+            let (start, end) = (fold(start)?, fold(end)?);
+            let end = match (limits, end) {
+                (RangeLimits::Closed(_), end) => Some(end),
+                (RangeLimits::HalfOpen(_), DiscriminantValue::Signed(end)) => end.checked_sub(1).map(DiscriminantValue::Signed),
+                (RangeLimits::HalfOpen(_), DiscriminantValue::Unsigned(end)) => end.checked_sub(1).map(DiscriminantValue::Unsigned),
+            }
+            .filter(|end| start <= *end)
+            .ok_or_else(|| syn::Error::new_spanned(expr, "Reserved ranges must not be empty"))?;
+            Ok(ReservedRange { start, end })
+        })
+        .collect()
+}
+
+/// Checks that no current variant uses a reserved discriminant or the discriminant of a retired
+/// variant.
+///
+/// Discriminants that were evaluated at macro time are compared directly. Any other discriminant
+/// is checked with `const` assertions instead, which are returned.
+///
+/// * `discriminants`: The discriminants of the current variants.
+/// * `retired`: The discriminants of the retired variants.
+/// * `reserved`: The reserved discriminants.
+/// * `item_ident`: The name of the enum.
+/// * `repr`: The repr for the enum.
+///
+/// # Errors
+/// Fails if an evaluated discriminant is retired or reserved.
+pub fn check_reserved_discriminants(discriminants: &[DiscriminantVariant], retired: &[DiscriminantVariant], reserved: &[ReservedRange], item_ident: &Ident, repr: &Ident) -> syn::Result<TokenStream> {
+    let mut error: Option<syn::Error> = None;
+    let mut assertions = quote! {};
+    for DiscriminantVariant { variant, expr, value, .. } in discriminants {
+        let ident = &variant.ident;
+        let span = ident.span();
+        for range in reserved {
+            let (start, end) = (range.start, range.end);
+            match value {
+                Some(value) if range.contains(*value) => {
+                    let reserved_error = syn::Error::new_spanned(ident, format!("`{ident}` has the discriminant `{value}`, which is reserved"));
+                    match &mut error {
+                        Some(error) => error.combine(reserved_error),
+                        None => error = Some(reserved_error),
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    let message = format!("`{ident}` of `{item_ident}` has a reserved discriminant");
+                    assertions = quote_spanned! {span=>
+                        #assertions
+                        const _: () = {
+                            let value: #repr = #expr;
+                            assert!(!matches!(value, #start..=#end), #message);
+                        };
+                    };
+                }
+            }
+        }
+        for retired in retired {
+            match (value, retired.value) {
+                (Some(value), Some(retired_value)) if *value == retired_value => {
+                    let retired_error = syn::Error::new_spanned(ident, format!("`{ident}` has the discriminant `{value}`, which was retired by `{}`", retired.variant.ident));
+                    match &mut error {
+                        Some(error) => error.combine(retired_error),
+                        None => error = Some(retired_error),
+                    }
+                }
+                (Some(_), Some(_)) => {}
+                _ => {
+                    let retired_expr = &retired.expr;
+                    let message = format!("`{ident}` of `{item_ident}` has the discriminant of the retired `{}`", retired.variant.ident);
+                    assertions = quote_spanned! {span=>
+                        #assertions
+                        const _: () = {
+                            let value: #repr = #expr;
+                            let retired: #repr = #retired_expr;
+                            assert!(value != retired, #message);
+                        };
+                    };
+                }
+            }
+        }
+    }
+    error.map_or(Ok(assertions), Err)
+}
+
+/// Generates a condition that is true when `value` is retired or reserved, or `None` if there are
+/// no such discriminants.
+///
+/// * `retired`: The discriminants of the retired variants.
+/// * `reserved`: The reserved discriminants.
+pub fn generate_retired_condition(retired: &[DiscriminantVariant], reserved: &[ReservedRange]) -> Option<TokenStream> {
+    let retired_exprs = retired.iter().map(|retired| &retired.expr);
+    let conditions = retired_exprs
+        .map(|expr| quote! { value == (#expr) })
+        .chain(reserved.iter().map(|ReservedRange { start, end }| quote! { matches!(value, #start..=#end) }))
+        .collect::<Vec<_>>();
+    if conditions.is_empty() { None } else { Some(quote! { #(#conditions)||* }) }
+}
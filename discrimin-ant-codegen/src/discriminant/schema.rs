@@ -0,0 +1,142 @@
+//! Computes `SCHEMA_HASH`, a fingerprint of an enum that peers can compare to check that they agree
+//! on its variants and discriminants.
+//!
+//! The hash is the 64-bit FNV-1a hash of the enum name, the repr, and the name and discriminant of
+//! each variant in declaration order, optionally followed by its fields. Each part is written as
+//! its length in bytes, as a little-endian `u64`, followed by its UTF-8 bytes. Field types are
+//! written from their tokens with whitespace only between adjacent words, so the hash depends on
+//! neither the platform nor how the compiler prints tokens.
+use proc_macro2::{Delimiter, Literal, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Fields, Ident};
+
+use super::DiscriminantVariant;
+
+// These are the same as the constants behind `discrimin_ant::combine_schema_hashes`, which cannot
+// be imported since `discrimin-ant` depends on this crate through `discrimin-ant-proc`. The
+// `schema_hasher_matches_combine_schema_hashes` test keeps the two in sync.
+/// The FNV-1a offset basis for 64-bit hashes.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The FNV-1a prime for 64-bit hashes.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Builds a 64-bit FNV-1a hash from length-prefixed parts.
+pub struct SchemaHasher(u64);
+
+impl SchemaHasher {
+    /// Creates a hasher that has not been given any parts.
+    pub const fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    /// Adds raw bytes to the hash.
+    ///
+    /// * `bytes`: The bytes to add.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Adds a part to the hash, prefixed with its length so that parts cannot run together.
+    ///
+    /// * `part`: The part to add.
+    fn write(&mut self, part: &str) {
+        self.write_bytes(&(part.len() as u64).to_le_bytes());
+        self.write_bytes(part.as_bytes());
+    }
+
+    /// Returns the hash of the parts added so far.
+    pub const fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Computes the schema hash of an enum.
+///
+/// Discriminants that cannot be evaluated at macro time are hashed from their expressions, such
+/// as `BASE + 1u8`.
+///
+/// * `item_ident`: The name of the enum.
+/// * `repr`: The repr for the enum.
+/// * `discriminants`: The discriminants of the current variants.
+/// * `include_fields`: Whether the names and types of the fields of each variant are hashed.
+pub fn schema_hash(item_ident: &Ident, repr: &Ident, discriminants: &[DiscriminantVariant], include_fields: bool) -> u64 {
+    let mut hasher = SchemaHasher::new();
+    hasher.write(&item_ident.to_string());
+    hasher.write(&repr.to_string());
+    for DiscriminantVariant { variant, expr, value, .. } in discriminants {
+        hasher.write(&variant.ident.to_string());
+        hasher.write(&value.map_or_else(|| canonical_tokens(expr.to_token_stream()), |value| value.to_string()));
+        if include_fields {
+            hasher.write(&canonical_fields(&variant.fields));
+        }
+    }
+    hasher.finish()
+}
+
+/// Writes the fields of a variant in a canonical form, e.g. `(u8,String)` or `{x:u32}`.
+///
+/// * `fields`: The fields of the variant.
+fn canonical_fields(fields: &Fields) -> String {
+    let parts = fields
+        .iter()
+        .map(|field| {
+            let ty = canonical_tokens(field.ty.to_token_stream());
+            field.ident.as_ref().map_or_else(|| ty.clone(), |ident| format!("{ident}:{ty}"))
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    match fields {
+        Fields::Named(_) => format!("{{{parts}}}"),
+        Fields::Unnamed(_) => format!("({parts})"),
+        Fields::Unit => String::new(),
+    }
+}
+
+/// Writes tokens in a canonical form, with a space only between two adjacent words, e.g.
+/// `Vec < & 'static str >` becomes `Vec<&'static str>`.
+///
+/// * `tokens`: The tokens to write.
+pub fn canonical_tokens(tokens: TokenStream) -> String {
+    let mut output = String::new();
+    let mut needs_space = false;
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                output.push_str(open);
+                output.push_str(&canonical_tokens(group.stream()));
+                output.push_str(close);
+                needs_space = false;
+            }
+            TokenTree::Punct(punct) => {
+                output.push(punct.as_char());
+                needs_space = false;
+            }
+            TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                if needs_space {
+                    output.push(' ');
+                }
+                output.push_str(&token.to_string());
+                needs_space = true;
+            }
+        }
+    }
+    output
+}
+
+/// Writes a schema hash as a hexadecimal literal, grouped in fours so that it stays readable, e.g.
+/// `0xcbf2_9ce4_8422_2325`.
+///
+/// * `hash`: The schema hash.
+pub fn hash_literal(hash: u64) -> Literal {
+    let digits = format!("{hash:016x}");
+    let groups = digits.as_bytes().chunks(4).map(|group| String::from_utf8_lossy(group).into_owned()).collect::<Vec<_>>();
+    format!("0x{}", groups.join("_")).parse().unwrap_or_else(|_| Literal::u64_suffixed(hash))
+}
@@ -0,0 +1,113 @@
+//! Generates `schemars::JsonSchema` implementations that describe the encoding produced by the
+//! `serde` option.
+//!
+//! The original enum is described as a `oneOf` with an entry for each variant: an array holding the
+//! discriminant as a `const`, followed by the schema of each field. The fieldless enum is described
+//! as an integer `enum` of its discriminants, along with the variant names if it is written as its
+//! name in human-readable formats.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, parse_quote};
+
+use super::options::SerdeOptions;
+use super::serde::with_type_param_bounds;
+use super::{DiscriminantVariant, OtherVariant};
+
+/// Generates `JsonSchema` for the original enum.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `item_ident`: The name of the original enum.
+/// * `generics`: The generics of the original enum. Each type parameter is required to implement
+///   `JsonSchema`.
+/// * `repr`: The repr for the enum.
+pub fn generate_schemars_impl(discriminants: &[DiscriminantVariant], item_ident: &Ident, generics: &Generics, repr: &Ident) -> TokenStream {
+    let variant_schemas = discriminants.iter().map(|DiscriminantVariant { variant, expr, .. }| {
+        let title = variant.ident.to_string();
+        let field_types = variant.fields.iter().map(|field| &field.ty);
+        let len = proc_macro2::Literal::usize_unsuffixed(variant.fields.len().saturating_add(1));
+        quote! {
+            {
+                "title": #title,
+                "type": "array",
+                "prefixItems": [
+                    { "const": ({ let discriminant: #repr = #expr; discriminant }) }
+                    #(, (generator.subschema_for::<#field_types>()))*
+                ],
+                "items": false,
+                "minItems": #len,
+                "maxItems": #len
+            }
+        }
+    });
+    let description = format!("A variant of {item_ident}, written as its discriminant followed by its fields.");
+
+    // Generic enums are inlined since their name alone does not tell their instances apart.
+    let (schema_name, inline_schema) = if generics.type_params().next().is_some() {
+        (quote! { core::any::type_name::<Self>() }, quote! { fn inline_schema() -> bool { true } })
+    } else {
+        (quote! { stringify!(#item_ident) }, quote! {})
+    };
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let schema_generics = with_type_param_bounds(generics.clone(), &parse_quote!(discrimin_ant::schemars::JsonSchema));
+    let (impl_generics, _, where_clause) = schema_generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics discrimin_ant::schemars::JsonSchema for #item_ident #ty_generics #where_clause {
+            #inline_schema
+
+            fn schema_name() -> discrimin_ant::Cow<'static, str> {
+                discrimin_ant::Cow::Borrowed(#schema_name)
+            }
+
+            fn json_schema(generator: &mut discrimin_ant::schemars::SchemaGenerator) -> discrimin_ant::schemars::Schema {
+                discrimin_ant::schemars::json_schema!({
+                    "description": #description,
+                    "oneOf": [#(#variant_schemas),*]
+                })
+            }
+        }
+    }
+}
+
+/// Generates `JsonSchema` for the fieldless enum.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `fieldless_ident`: The name of the fieldless enum.
+/// * `repr`: The repr for the enum.
+/// * `options`: The `serde` options given to the attribute.
+/// * `other`: The catch-all variant for unknown discriminants, if any. When present, any value of
+///   the repr is accepted.
+pub fn generate_fieldless_schemars_impl(discriminants: &[DiscriminantVariant], fieldless_ident: &Ident, repr: &Ident, options: &SerdeOptions, other: Option<&OtherVariant>) -> TokenStream {
+    let discriminant_schema = if other.is_some() {
+        quote! { (generator.subschema_for::<#repr>()) }
+    } else {
+        quote! { { "type": "integer", "enum": (Self::DISCRIMINANTS) } }
+    };
+    let schema = if options.human_readable {
+        let variant_names = discriminants.iter().map(|discriminant| &discriminant.name);
+        quote! {
+            discrimin_ant::schemars::json_schema!({
+                "oneOf": [
+                    { "type": "string", "enum": [#(#variant_names),*] },
+                    #discriminant_schema
+                ]
+            })
+        }
+    } else if other.is_some() {
+        quote! { generator.subschema_for::<#repr>() }
+    } else {
+        quote! { discrimin_ant::schemars::json_schema!(#discriminant_schema) }
+    };
+
+    quote! {
+        impl discrimin_ant::schemars::JsonSchema for #fieldless_ident {
+            fn schema_name() -> discrimin_ant::Cow<'static, str> {
+                discrimin_ant::Cow::Borrowed(stringify!(#fieldless_ident))
+            }
+
+            fn json_schema(generator: &mut discrimin_ant::schemars::SchemaGenerator) -> discrimin_ant::schemars::Schema {
+                #schema
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Generates `serde` implementations that marshal an enum by its discriminant instead of its
+//! variant index.
+//!
+//! Each variant is written as a tuple containing the discriminant followed by each of the
+//! variant's fields in declaration order. Field names are not written, so named and unnamed
+//! variants produce the same layout.
+//!
+//! The fieldless enum is written as its discriminant, or optionally as its variant name when the
+//! format is human-readable.
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Fields, GenericParam, Generics, Ident, LifetimeParam, parse_quote};
+
+use super::options::SerdeOptions;
+use super::{DiscriminantVariant, OtherVariant};
+
+/// Generates `Serialize` and `Deserialize` for the original enum.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `item_ident`: The name of the original enum.
+/// * `generics`: The generics of the original enum. Each type parameter is required to implement
+///   the respective `serde` trait.
+/// * `fieldless_ident`: The name of the fieldless enum, used to select the variant when
+///   deserializing.
+/// * `repr`: The repr for the enum.
+pub fn generate_serde_impls(discriminants: &[DiscriminantVariant], item_ident: &Ident, generics: &Generics, fieldless_ident: &Ident, repr: &Ident) -> TokenStream {
+    let serialize_arms = generate_serialize_arms(discriminants);
+    let deserialize_arms = generate_deserialize_arms(discriminants, item_ident, fieldless_ident);
+    let max_len = discriminants.iter().map(|discriminant| discriminant.variant.fields.len()).max().unwrap_or_default().saturating_add(1);
+    let expecting = format!("a discriminant followed by the fields of {item_ident}");
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let serialize_generics = with_type_param_bounds(generics.clone(), &parse_quote!(discrimin_ant::serde::Serialize));
+    let (serialize_impl_generics, _, serialize_where_clause) = serialize_generics.split_for_impl();
+    let deserialize_generics = with_deserialize_lifetime(with_type_param_bounds(generics.clone(), &parse_quote!(discrimin_ant::serde::Deserialize<'de>)));
+    let (deserialize_impl_generics, visitor_ty_generics, deserialize_where_clause) = deserialize_generics.split_for_impl();
+
+    quote! {
+        impl #serialize_impl_generics discrimin_ant::serde::Serialize for #item_ident #ty_generics #serialize_where_clause {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: discrimin_ant::serde::Serializer,
+            {
+                use discrimin_ant::serde::ser::SerializeTuple;
+                match self {
+                    #serialize_arms
+                }
+            }
+        }
+
+        impl #deserialize_impl_generics discrimin_ant::serde::Deserialize<'de> for #item_ident #ty_generics #deserialize_where_clause {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: discrimin_ant::serde::Deserializer<'de>,
+            {
+                struct DiscriminantVisitor #deserialize_impl_generics #deserialize_where_clause {
+                    value: core::marker::PhantomData<fn() -> #item_ident #ty_generics>,
+                    lifetime: core::marker::PhantomData<&'de ()>,
+                }
+
+                impl #deserialize_impl_generics discrimin_ant::serde::de::Visitor<'de> for DiscriminantVisitor #visitor_ty_generics #deserialize_where_clause {
+                    type Value = #item_ident #ty_generics;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                        formatter.write_str(#expecting)
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: discrimin_ant::serde::de::SeqAccess<'de>,
+                    {
+                        use discrimin_ant::serde::de::Error;
+                        let discriminant: #repr = seq.next_element()?.ok_or_else(|| Error::invalid_length(0, &self))?;
+                        match #fieldless_ident::try_from(discriminant) {
+                            #deserialize_arms
+                            _ => Err(Error::custom(format_args!("unknown discriminant `{}` for `{}`", discriminant, stringify!(#item_ident)))),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_tuple(
+                    #max_len,
+                    DiscriminantVisitor {
+                        value: core::marker::PhantomData,
+                        lifetime: core::marker::PhantomData,
+                    },
+                )
+            }
+        }
+    }
+}
+
+/// Generates `Serialize` and `Deserialize` for the fieldless enum.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `fieldless_ident`: The name of the fieldless enum.
+/// * `repr`: The repr for the enum.
+/// * `options`: The `serde` options given to the attribute.
+/// * `other`: The catch-all variant for unknown discriminants, if any. It is always written as its
+///   discriminant.
+pub fn generate_fieldless_serde_impls(discriminants: &[DiscriminantVariant], fieldless_ident: &Ident, repr: &Ident, options: &SerdeOptions, other: Option<&OtherVariant>) -> TokenStream {
+    let variant_idents = discriminants.iter().map(|discriminant| &discriminant.variant.ident).collect::<Vec<_>>();
+    let variant_names = discriminants.iter().map(|discriminant| &discriminant.name).collect::<Vec<_>>();
+
+    let is_other = other.map_or_else(|| quote! {}, |OtherVariant { ident, .. }| quote! { && !matches!(self, Self::#ident(_)) });
+    let serialize_name = if options.human_readable {
+        quote! {
+            if serializer.is_human_readable() #is_other {
+                return serializer.serialize_str(self.name());
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let deserialize = if options.human_readable {
+        let expecting = format!("a variant name or discriminant of {fieldless_ident}");
+        quote! {
+            struct NameOrDiscriminantVisitor;
+
+            impl NameOrDiscriminantVisitor {
+                fn from_discriminant<I, E>(self, value: I) -> Result<#fieldless_ident, E>
+                where
+                    I: Copy + core::fmt::Display,
+                    #repr: TryFrom<I>,
+                    E: discrimin_ant::serde::de::Error,
+                {
+                    #repr::try_from(value)
+                        .ok()
+                        .and_then(|discriminant| #fieldless_ident::try_from(discriminant).ok())
+                        .ok_or_else(|| E::custom(format_args!("unknown discriminant `{}` for `{}`", value, stringify!(#fieldless_ident))))
+                }
+            }
+
+            impl<'de> discrimin_ant::serde::de::Visitor<'de> for NameOrDiscriminantVisitor {
+                type Value = #fieldless_ident;
+
+                fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                    formatter.write_str(#expecting)
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: discrimin_ant::serde::de::Error,
+                {
+                    match value {
+                        #(#variant_names => Ok(#fieldless_ident::#variant_idents),)*
+                        _ => Err(E::unknown_variant(value, &[#(#variant_names),*])),
+                    }
+                }
+
+                fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+                where
+                    E: discrimin_ant::serde::de::Error,
+                {
+                    self.from_discriminant(value)
+                }
+
+                fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E>
+                where
+                    E: discrimin_ant::serde::de::Error,
+                {
+                    self.from_discriminant(value)
+                }
+
+                fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+                where
+                    E: discrimin_ant::serde::de::Error,
+                {
+                    self.from_discriminant(value)
+                }
+
+                fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
+                where
+                    E: discrimin_ant::serde::de::Error,
+                {
+                    self.from_discriminant(value)
+                }
+            }
+
+            if deserializer.is_human_readable() {
+                return deserializer.deserialize_any(NameOrDiscriminantVisitor);
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        impl discrimin_ant::serde::Serialize for #fieldless_ident {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: discrimin_ant::serde::Serializer,
+            {
+                #serialize_name
+                discrimin_ant::serde::Serialize::serialize(&self.discriminant(), serializer)
+            }
+        }
+
+        impl<'de> discrimin_ant::serde::Deserialize<'de> for #fieldless_ident {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: discrimin_ant::serde::Deserializer<'de>,
+            {
+                use discrimin_ant::serde::de::Error;
+                #deserialize
+                let discriminant = <#repr as discrimin_ant::serde::Deserialize>::deserialize(deserializer)?;
+                Self::try_from(discriminant).map_err(|_| Error::custom(format_args!("unknown discriminant `{}` for `{}`", discriminant, stringify!(#fieldless_ident))))
+            }
+        }
+    }
+}
+
+/// Generates the match arms that serialize each variant as its discriminant followed by its
+/// fields.
+///
+/// * `discriminants`: The discriminants of the original enum.
+fn generate_serialize_arms(discriminants: &[DiscriminantVariant]) -> TokenStream {
+    let mut arms = quote! {};
+    for DiscriminantVariant { variant, .. } in discriminants {
+        let ident = &variant.ident;
+        let bindings = field_bindings(&variant.fields);
+        let pattern = variant_pattern(&variant.fields, &bindings);
+        let len = bindings.len().saturating_add(1);
+        arms = quote! {
+            #arms
+            Self::#ident #pattern => {
+                let mut tuple = serializer.serialize_tuple(#len)?;
+                tuple.serialize_element(&self.discriminant())?;
+                #(tuple.serialize_element(#bindings)?;)*
+                tuple.end()
+            }
+        };
+    }
+    arms
+}
+
+/// Generates the match arms that read the fields of each variant once its discriminant is known.
+///
+/// * `discriminants`: The discriminants of the original enum.
+/// * `item_ident`: The name of the original enum.
+/// * `fieldless_ident`: The name of the fieldless enum.
+fn generate_deserialize_arms(discriminants: &[DiscriminantVariant], item_ident: &Ident, fieldless_ident: &Ident) -> TokenStream {
+    let mut arms = quote! {};
+    for DiscriminantVariant { variant, .. } in discriminants {
+        let ident = &variant.ident;
+        let bindings = field_bindings(&variant.fields);
+        let pattern = variant_pattern(&variant.fields, &bindings);
+        let indices = (1..=bindings.len()).collect::<Vec<_>>();
+        arms = quote! {
+            #arms
+            Ok(#fieldless_ident::#ident) => {
+                #(let #bindings = seq.next_element()?.ok_or_else(|| Error::invalid_length(#indices, &self))?;)*
+                Ok(#item_ident::#ident #pattern)
+            }
+        };
+    }
+    arms
+}
+
+/// Requires every type parameter in `generics` to implement `bound`.
+///
+/// * `generics`: The generics to add the bounds to.
+/// * `bound`: The trait each type parameter must implement.
+pub fn with_type_param_bounds(mut generics: Generics, bound: &syn::TraitBound) -> Generics {
+    let type_params = generics.type_params().map(|param| param.ident.clone()).collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for ident in type_params {
+        where_clause.predicates.push(parse_quote!(#ident: #bound));
+    }
+    generics
+}
+
+/// Adds the `'de` lifetime used by `serde::Deserialize`. `'de` outlives every other lifetime so
+/// that borrowed fields, like `&'a str`, can be deserialized.
+///
+/// * `generics`: The generics to add the lifetime to.
+fn with_deserialize_lifetime(mut generics: Generics) -> Generics {
+    let mut lifetime: LifetimeParam = parse_quote!('de);
+    lifetime.bounds.extend(generics.lifetimes().map(|param| param.lifetime.clone()));
+    generics.params.insert(0, GenericParam::Lifetime(lifetime));
+    generics
+}
+
+/// Creates a binding name for each field of a variant.
+///
+/// * `fields`: The fields of the variant.
+pub fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    (0..fields.len()).map(|i| format_ident!("__field{i}")).collect()
+}
+
+/// Generates a pattern (or constructor) binding each field of a variant to the given names.
+///
+/// * `fields`: The fields of the variant.
+/// * `bindings`: The names to bind each field to.
+pub fn variant_pattern(fields: &Fields, bindings: &[Ident]) -> TokenStream {
+    match fields {
+        Fields::Named(named) => {
+            let field_names = named.named.iter().map(|field| &field.ident);
+            quote! { { #(#field_names: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { (#(#bindings),*) },
+        Fields::Unit => quote! {},
+    }
+}
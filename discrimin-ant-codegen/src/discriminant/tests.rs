@@ -0,0 +1,985 @@
+#[expect(dead_code, clippy::doc_markdown, clippy::trivially_copy_pass_by_ref)]
+mod complex_u_enum;
+#[expect(clippy::doc_markdown, clippy::trivially_copy_pass_by_ref)]
+mod simple_fieldless_enum;
+#[expect(dead_code, clippy::doc_markdown, clippy::trivially_copy_pass_by_ref)]
+mod simple_no_discriminant_enum;
+#[expect(dead_code, clippy::doc_markdown, clippy::trivially_copy_pass_by_ref)]
+mod simple_signed_fieldless_enum;
+/// The open newtype generated on its own for the enum of `simple_fieldless_enum`.
+#[expect(clippy::doc_markdown)]
+mod simple_fieldless_enum_code {
+    use super::simple_fieldless_enum::{SimpleFieldlessEnum, SimpleFieldlessEnum_};
+
+    include!("tests/simple_fieldless_enum_code.rs");
+}
+
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+/// Converts a str representation of the expected output into the actual form.
+///
+/// * `expected_str`: The string to expect, typically read from a file.
+fn str_to_expected(expected_str: &str) -> String {
+    #[allow(clippy::unwrap_used)]
+    let expected = expected_str.parse::<TokenStream>().unwrap();
+    let expected = expected.to_string();
+    expected.replace(":<", ": <").replace("<&", "< &").replace("&'", "& '").replace("<'", "< '").replace(">;", "> ;").replace(">::", "> ::")
+}
+
+#[test]
+fn bad_repr_type() {
+    let tokens = quote! { enum SimpleEnum{ A, B } };
+    let attr = quote! { transparent };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! {
+        ::core::compile_error! {
+            "Unsupported discriminant type `transparent` for `SimpleEnum`. Must be a primitive integer type. See <https://doc.rust-lang.org/reference/type-layout.html#primitive-representations>"
+        }
+    };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn not_an_enum() {
+    let tokens = quote! { struct SimpleStruct { a: u8 } };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "Only enums are supported by `discriminant`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn too_many_variants() {
+    // 256 implicit variants fit in a u8, but the 257th does not
+    let variants = (0..=256).map(|i| quote::format_ident!("V{i}"));
+    let tokens = quote! { enum SimpleEnum { #(#variants),* } };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "Too many variants! `V256` does not fit in the discriminant type `u8`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn unknown_option() {
+    let tokens = quote! { enum SimpleEnum { A, B } };
+    let attr = quote! { u8, frobnicate };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "Unsupported `discriminant` option" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn architecture_dependent_repr_warns() {
+    let tokens = quote! { enum SimpleEnum { A, B } };
+    let attr = quote! { usize };
+    let result = discriminant_impl(attr, tokens).to_string();
+
+    assert!(!result.contains("compile_error"));
+    assert!(result.contains("deprecated"));
+    assert!(result.contains("The usize discriminant type varies in size between architectures."));
+}
+
+#[test]
+fn fieldless_variants() {
+    let tokens = quote! { enum SimpleEnum{ A, B } };
+    let attr = quote! { u8 };
+    let _ = discriminant_impl(attr, tokens).to_string();
+}
+
+#[test]
+fn assigned_fieldless_variants() {
+    let tokens = quote! {
+        pub enum SimpleFieldlessEnum {
+            One = 1,
+            Two,
+            Five = 5,
+            Six,
+        }
+    };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = str_to_expected(include_str!("./tests/simple_fieldless_enum.rs"));
+    assert_eq!(result.to_string(), expected);
+}
+
+#[test]
+fn assigned_variants() {
+    let tokens = quote! {
+        pub enum ComplexUEnum {
+            One(i32) = 1,
+            Two(i32),
+            Five { x: u32 } = 5,
+            Six { x: u32 },
+        }
+    };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = str_to_expected(include_str!("./tests/complex_u_enum.rs"));
+    assert_eq!(result.to_string(), expected);
+}
+
+#[test]
+fn assigned_signed_fieldless_variants() {
+    let tokens = quote! {
+        pub enum SimpleSignedFieldlessEnum {
+            NOne = -1,
+            One = 1,
+            Two,
+            Five = 5,
+            Six,
+            Seven,
+        }
+    };
+    let attr = quote! { i8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = str_to_expected(include_str!("./tests/simple_signed_fieldless_enum.rs"));
+    assert_eq!(result.to_string(), expected);
+}
+
+#[test]
+fn simple_no_discriminant_enum() {
+    let tokens = quote! {
+        pub enum SimpleNoDiscriminantEnum {
+            Zero,
+            One,
+            Two,
+            Three = 3,
+            Four,
+            Five,
+        }
+    };
+    let attr = quote! { u16 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = str_to_expected(include_str!("./tests/simple_no_discriminant_enum.rs"));
+    assert_eq!(result.to_string(), expected);
+}
+
+#[test]
+fn serde_requires_fieldless() {
+    let tokens = quote! { enum SimpleEnum { A, B } };
+    let attr = quote! { u8, serde, fieldless(skip) };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "The `serde` option requires the fieldless enum. Remove `fieldless(skip)`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn duplicate_discriminants() {
+    let tokens = quote! { enum SimpleEnum { A = 1, B = 0, C, D = 2 - 1 } };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! {
+        ::core::compile_error! { "`C` has the discriminant `1`, which is already used by `A`" }
+        ::core::compile_error! { "`D` has the discriminant `1`, which is already used by `A`" }
+    };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn implicit_discriminant_overflow() {
+    let tokens = quote! { enum SimpleEnum { A = 254, B, C } };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "The implicit discriminant of `C` overflows `u8`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn explicit_discriminant_out_of_range() {
+    let tokens = quote! { enum SimpleEnum { A = -129, B } };
+    let attr = quote! { i8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "The discriminant `-129` does not fit in `i8`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn non_literal_discriminants_are_asserted() {
+    let tokens = quote! { enum SimpleEnum { A = FIRST, B, C = 7 } };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens).to_string();
+
+    let expected = quote! {
+        const _: () = {
+            let first: u8 = FIRST;
+            let second: u8 = FIRST + 1u8;
+            assert!(first != second, "`A` and `B` of `SimpleEnum` have the same discriminant");
+        };
+        const _: () = {
+            let first: u8 = FIRST;
+            let second: u8 = 7;
+            assert!(first != second, "`A` and `C` of `SimpleEnum` have the same discriminant");
+        };
+        const _: () = {
+            let first: u8 = FIRST + 1u8;
+            let second: u8 = 7;
+            assert!(first != second, "`B` and `C` of `SimpleEnum` have the same discriminant");
+        };
+    };
+    assert!(result.contains(&expected.to_string()), "{result}");
+}
+
+#[test]
+fn existing_repr() {
+    let tokens = quote! {
+        #[repr(u8)]
+        pub enum SimpleFieldlessEnum {
+            One = 1,
+            Two,
+            Five = 5,
+            Six,
+        }
+    };
+    let result = discriminant_impl(quote! {}, tokens);
+
+    let expected = str_to_expected(include_str!("./tests/simple_fieldless_enum.rs"));
+    assert_eq!(result.to_string(), expected);
+}
+
+#[test]
+fn combined_repr() {
+    let tokens = quote! {
+        #[repr(C, u16)]
+        enum SimpleEnum { A, B }
+    };
+    let attr = quote! { u16 };
+    let result = discriminant_impl(attr, tokens).to_string();
+
+    let expected = quote! {
+        #[repr(C, u16)]
+        enum SimpleEnum { A, B }
+    };
+    assert!(result.starts_with(&expected.to_string()), "{result}");
+}
+
+#[test]
+fn merged_repr() {
+    let tokens = quote! {
+        #[repr(C)]
+        enum SimpleEnum { A, B }
+    };
+    let attr = quote! { i32 };
+    let result = discriminant_impl(attr, tokens).to_string();
+
+    let expected = quote! {
+        #[repr(C, i32)]
+        enum SimpleEnum { A, B }
+    };
+    assert!(result.starts_with(&expected.to_string()), "{result}");
+}
+
+#[test]
+fn conflicting_repr() {
+    let tokens = quote! {
+        #[repr(u8)]
+        enum SimpleEnum { A, B }
+    };
+    let attr = quote! { u16 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "The discriminant type `u16` conflicts with the existing `#[repr(u8)]`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn missing_repr() {
+    let tokens = quote! { enum SimpleEnum { A, B } };
+    let result = discriminant_impl(quote! {}, tokens);
+
+    let expected = quote! { ::core::compile_error! { "Missing discriminant type. Expected something like `#[discriminant(u8)]` or an existing `#[repr(u8)]`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn derive_matches_attribute() {
+    let item = quote! {
+        pub enum ComplexUEnum {
+            One(i32) = 1,
+            Two(i32),
+            Five { x: u32 } = 5,
+            Six { x: u32 },
+        }
+    };
+    let derived = derive_discriminant_impl(quote! {
+        #[discriminant(repr = u8)]
+        #[repr(u8)]
+        #item
+    });
+    let result = quote! {
+        #[repr(u8)]
+        #item
+        #derived
+    };
+
+    let expected = str_to_expected(include_str!("./tests/complex_u_enum.rs"));
+    assert_eq!(result.to_string(), expected);
+}
+
+#[test]
+fn derive_requires_repr() {
+    let tokens = quote! {
+        #[discriminant(repr = u8)]
+        enum SimpleEnum { A, B }
+    };
+    let result = derive_discriminant_impl(tokens);
+
+    let expected = quote! { ::core::compile_error! { "`#[derive(Discriminant)]` cannot add a `repr`. Add `#[repr(u8)]` to the enum" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn case_conversion() {
+    let cases = [
+        ("Ping", "ping", "ping", "PING"),
+        ("MoveTo", "move_to", "move-to", "MOVE_TO"),
+        ("HTTPRequest", "http_request", "http-request", "HTTP_REQUEST"),
+        ("Value2", "value2", "value2", "VALUE2"),
+        ("V2Header", "v2_header", "v2-header", "V2_HEADER"),
+        ("NOne", "n_one", "n-one", "N_ONE"),
+    ];
+    for (name, snake, kebab, screaming) in cases {
+        assert_eq!(Case::Snake.apply(name), snake);
+        assert_eq!(Case::Kebab.apply(name), kebab);
+        assert_eq!(Case::ScreamingSnake.apply(name), screaming);
+    }
+}
+
+#[test]
+fn unsupported_case() {
+    let tokens = quote! { enum SimpleEnum { A, B } };
+    let attr = quote! { u8, rename_all = "camelCase" };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "Unsupported case `camelCase`. Expected one of `snake_case`, `kebab-case` or `SCREAMING_SNAKE_CASE`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn duplicate_names() {
+    let tokens = quote! {
+        enum SimpleEnum {
+            MoveTo,
+            #[discriminant(rename = "move_to")]
+            Move,
+        }
+    };
+    let attr = quote! { u8, rename_all = "snake_case" };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "`Move` has the name `move_to`, which is already used by `MoveTo`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn variant_options_are_removed() {
+    let tokens = quote! {
+        enum SimpleEnum {
+            #[discriminant(rename = "a")]
+            A,
+            B,
+        }
+    };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens).to_string();
+
+    assert!(result.starts_with(&quote! { #[repr(u8)] enum SimpleEnum { A, B, } }.to_string()));
+    assert!(result.contains(&quote! { Self::A => "a", }.to_string()));
+}
+
+#[test]
+fn other_variant_clashes() {
+    let tokens = quote! { enum SimpleEnum { A, Unknown } };
+    let attr = quote! { u8, other = Unknown };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "`Unknown` clashes with the variant `Unknown`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn other_variant_is_infallible() {
+    let tokens = quote! { enum SimpleEnum { A, B } };
+    let attr = quote! { u8, other = Unknown, fieldless(error = MyError) };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "`fieldless(error)` cannot be used with `other` since every discriminant converts" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn code_constant_clashes() {
+    let tokens = quote! { enum SimpleEnum { ValueA, Value_A } };
+    let attr = quote! { u8, code };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "`Value_A` and `ValueA` both produce the constant `VALUE_A`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn code_newtype() {
+    let tokens = quote! {
+        pub enum SimpleFieldlessEnum {
+            One = 1,
+            Two,
+            Five = 5,
+            Six,
+        }
+    };
+    #[allow(clippy::unwrap_used)]
+    let item: DeriveInput = syn::parse2(tokens).unwrap();
+    #[allow(clippy::unwrap_used)]
+    let analyzed = DiscriminantEnum::from_attribute(quote! { u8 }, &item).unwrap();
+    let variants = analyzed.current_variants();
+    let hash = schema::schema_hash(&item.ident, &analyzed.repr, &variants, false);
+    let code_ident = format_ident!("SimpleFieldlessEnumCode");
+    let fieldless_ident = format_ident!("SimpleFieldlessEnum_");
+    #[allow(clippy::unwrap_used)]
+    let result = generate_code_newtype(&item, &variants, &analyzed.repr, &code_ident, &fieldless_ident, None, hash).unwrap();
+
+    let expected = str_to_expected(include_str!("./tests/simple_fieldless_enum_code.rs"));
+    assert_eq!(result.to_string(), expected);
+}
+
+#[test]
+fn code_raw_identifiers() {
+    let tokens = quote! { enum Kw { r#async, r#yield, Plain } };
+    let result = discriminant_impl(quote! { u8, code }, tokens).to_string();
+    assert!(!result.contains("compile_error"), "{result}");
+    assert!(result.contains(&quote! { pub const ASYNC: Self = Self(0u8); }.to_string()), "{result}");
+    assert!(result.contains(&quote! { pub const YIELD: Self = Self(1u8); }.to_string()), "{result}");
+
+    let result = discriminant_impl(quote! { u8, code }, quote! { enum SimpleEnum { _1 } });
+    let expected = quote! { ::core::compile_error! { "`_1` produces the constant `1`, which is not a valid identifier" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn explicit_requires_discriminants() {
+    let tokens = quote! { enum SimpleEnum { A = 1, B, C = 5 } };
+    let attr = quote! { u8, explicit };
+    let result = discriminant_impl(attr.clone(), tokens);
+
+    let expected = quote! { ::core::compile_error! { "`B` must have an explicit discriminant. Add `= 2` to keep its current value" } };
+    assert_eq!(result.to_string(), expected.to_string());
+
+    let tokens = quote! { enum SimpleEnum { A = BASE, B } };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "`B` must have an explicit discriminant. Add `= BASE + 1u8` to keep its current value" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn implicit_overrides_explicit() {
+    let tokens = quote! { enum SimpleEnum { A = 1, B } };
+    let attr = quote! { u8, explicit, implicit };
+    let result = discriminant_impl(attr, tokens).to_string();
+
+    assert!(!result.contains("compile_error"));
+    assert!(!result.contains("DISCRIMIN_ANT_EXPLICIT"));
+}
+
+#[test]
+fn explicit_from_env() {
+    let workspace = std::path::Path::new("/home/user/project/crates/app");
+    assert!(options::explicit_from_env(Some(workspace), Some("1")));
+    assert!(options::explicit_from_env(Some(workspace), Some("True")));
+    assert!(!options::explicit_from_env(Some(workspace), Some("0")));
+    assert!(!options::explicit_from_env(Some(workspace), None));
+    assert!(!options::explicit_from_env(None, Some("1")));
+
+    let registry = std::path::Path::new("/home/user/.cargo/registry/src/index.crates.io-1949cf8c6b5b557f/dep-1.0.0");
+    assert!(!options::explicit_from_env(Some(registry), Some("1")));
+    let git = std::path::Path::new("/home/user/.cargo/git/checkouts/dep-0123456789abcdef/fedcba9");
+    assert!(!options::explicit_from_env(Some(git), Some("1")));
+}
+
+#[test]
+fn reserved_discriminants() {
+    let tokens = quote! { enum SimpleEnum { A = 1, B, C = 12, D = 7 } };
+    let attr = quote! { u8, reserved(2, 7, 10..=19) };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! {
+        ::core::compile_error! { "`B` has the discriminant `2`, which is reserved" }
+        ::core::compile_error! { "`C` has the discriminant `12`, which is reserved" }
+        ::core::compile_error! { "`D` has the discriminant `7`, which is reserved" }
+    };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn invalid_reserved_discriminants() {
+    let tokens = quote! { enum SimpleEnum { A, B } };
+    let attr = quote! { u8, reserved(BASE) };
+    let result = discriminant_impl(attr, tokens.clone());
+
+    let expected = quote! { ::core::compile_error! { "Reserved discriminants must be integers or ranges of integers, such as `3` or `10..=19`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+
+    let attr = quote! { u8, reserved(5..5) };
+    let result = discriminant_impl(attr, tokens.clone());
+
+    let expected = quote! { ::core::compile_error! { "Reserved ranges must not be empty" } };
+    assert_eq!(result.to_string(), expected.to_string());
+
+    let attr = quote! { u8, reserved(300) };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "The discriminant `300` does not fit in `u8`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn retired_discriminants_cannot_be_reused() {
+    let tokens = quote! {
+        enum SimpleEnum {
+            A = 1,
+            #[discriminant(retired)]
+            B,
+            C = 2,
+        }
+    };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens);
+
+    let expected = quote! { ::core::compile_error! { "`C` has the discriminant `2`, which was retired by `B`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn retired_variants_are_removed() {
+    let tokens = quote! {
+        enum SimpleEnum {
+            A = 1,
+            #[discriminant(retired)]
+            B,
+            C,
+        }
+    };
+    let attr = quote! { u8 };
+    let result = discriminant_impl(attr, tokens).to_string();
+
+    assert!(result.starts_with(&quote! { #[repr(u8)] enum SimpleEnum { A = 1, C = 1 + 2u8 } }.to_string()));
+}
+
+#[test]
+fn derive_cannot_retire() {
+    let tokens = quote! {
+        #[repr(u8)]
+        enum SimpleEnum {
+            A,
+            #[discriminant(retired)]
+            B,
+        }
+    };
+    let result = derive_discriminant_impl(tokens);
+
+    let expected = quote! { ::core::compile_error! { "`#[derive(Discriminant)]` cannot remove retired variants. Use the `discriminant` attribute instead" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn lock_file_parsing() {
+    #[allow(clippy::unwrap_used)]
+    let lock = lock::LockFile::parse("# Comment\n\n[Message]\nPing = 1\n  Data = -2\n\n[Other]\nA = 0\n").unwrap();
+    let message = lock.sections["Message"].iter().map(|(name, expr)| (name.as_str(), quote! { #expr }.to_string())).collect::<Vec<_>>();
+    assert_eq!(message, [("Ping", "1".to_string()), ("Data", "- 2".to_string())]);
+    assert_eq!(lock.sections["Other"].len(), 1);
+
+    assert_eq!(lock::LockFile::parse("Ping = 1").err().as_deref(), Some("line 1 is not in a section. Add a section like `[Message]` above it"));
+    assert_eq!(lock::LockFile::parse("[Message]\nPing").err().as_deref(), Some("line 2 should be a section like `[Message]` or a variant like `Ping = 1`"));
+    assert_eq!(lock::LockFile::parse("[Message]\nPing = ?").err().as_deref(), Some("line 2 has an invalid discriminant `?`"));
+    assert_eq!(lock::LockFile::parse("[Message]\n[Message]").err().as_deref(), Some("line 2 repeats the section `[Message]`"));
+}
+
+#[test]
+fn locked_discriminants() {
+    let attr = quote! { u8, lock = "src/discriminant/tests/discriminants.lock" };
+    let result = discriminant_impl(attr.clone(), quote! { enum SimpleEnum { A = 1, B } }).to_string();
+    assert!(result.contains("include_bytes"));
+
+    let result = discriminant_impl(attr.clone(), quote! { enum SimpleEnum { A = 1, B = 3 } });
+    let expected = quote! { ::core::compile_error! { "`B` is locked to the discriminant `2` in `src/discriminant/tests/discriminants.lock`, but it is now `3`. Give it `= 2` to restore it" } };
+    assert_eq!(result.to_string(), expected.to_string());
+
+    let result = discriminant_impl(attr.clone(), quote! { enum SimpleEnum { A = 1 } });
+    let expected = quote! { ::core::compile_error! { "`B` is locked in `src/discriminant/tests/discriminants.lock` but no longer exists. Keep the variant and mark it `#[discriminant(retired)]` so that its discriminant is not reused" } };
+    assert_eq!(result.to_string(), expected.to_string());
+
+    let result = discriminant_impl(attr.clone(), quote! { enum SimpleEnum { A = 1, B, C } });
+    let expected = quote! { ::core::compile_error! { "`C` is not in the lock file `src/discriminant/tests/discriminants.lock`. Add `C = 3` to the `[SimpleEnum]` section to lock its discriminant" } };
+    assert_eq!(result.to_string(), expected.to_string());
+
+    let result = discriminant_impl(attr, quote! { enum Unlocked { A } }).to_string();
+    assert!(result.contains("`[Unlocked]` is not in the lock file `src/discriminant/tests/discriminants.lock`. Add:\\n\\n[Unlocked]\\nA = 0"));
+}
+
+#[test]
+fn locked_discriminants_section() {
+    let attr = quote! { u8, lock(path = "src/discriminant/tests/discriminants.lock", section = "legacy::SimpleEnum") };
+    let result = discriminant_impl(attr.clone(), quote! { enum SimpleEnum { A = 1, B = 3 } }).to_string();
+    assert!(!result.contains("compile_error"), "{result}");
+
+    let result = discriminant_impl(attr, quote! { enum SimpleEnum { A = 1, B, C } });
+    let expected = quote! { ::core::compile_error! { "`B` is locked to the discriminant `3` in `src/discriminant/tests/discriminants.lock`, but it is now `2`. Give it `= 3` to restore it" } };
+    assert!(result.to_string().starts_with(&expected.to_string()), "{result}");
+    assert!(result.to_string().contains("Add `C = 3` to the `[legacy::SimpleEnum]` section"));
+
+    let result = discriminant_impl(quote! { u8, lock(section = "legacy::SimpleEnum") }, quote! { enum SimpleEnum { A } });
+    let expected = quote! { ::core::compile_error! { "The `lock` option requires a `path`, e.g. `lock(path = \"discriminants.lock\")`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn schema_hash() {
+    let hash_of = |attr: TokenStream, tokens: TokenStream| {
+        let result = discriminant_impl(attr, tokens).to_string();
+        let start = result.find("pub const SCHEMA_HASH : u64 =").map(|start| start + "pub const SCHEMA_HASH : u64 =".len());
+        start.and_then(|start| result[start..].split(';').next().map(str::trim).map(str::to_string))
+    };
+    let fields = quote! { enum SimpleEnum { A(u8) = 1, B { x: Vec<&'static str> } } };
+    let other_fields = quote! { enum SimpleEnum { A(u16) = 1, B { x: Vec<&'static str> } } };
+
+    assert_eq!(hash_of(quote! { u8 }, fields.clone()).as_deref(), Some("0xdf99_1073_bf78_0db5"));
+    assert_eq!(hash_of(quote! { u8 }, fields.clone()), hash_of(quote! { u8 }, other_fields.clone()));
+    assert_ne!(hash_of(quote! { u8 }, fields.clone()), hash_of(quote! { u16 }, fields.clone()));
+    assert_ne!(hash_of(quote! { u8 }, fields.clone()), hash_of(quote! { u8 }, quote! { enum SimpleEnum { A(u8) = 1, B { x: Vec<&'static str> } = 3 } }));
+    assert_eq!(hash_of(quote! { u8, schema_hash(fields) }, fields.clone()).as_deref(), Some("0x388c_7ce0_77d1_489e"));
+    assert_ne!(hash_of(quote! { u8, schema_hash(fields) }, fields.clone()), hash_of(quote! { u8, schema_hash(fields) }, other_fields));
+    assert_ne!(hash_of(quote! { u8, schema_hash(fields) }, fields.clone()), hash_of(quote! { u8 }, fields));
+}
+
+#[test]
+fn schema_hasher_matches_combine_schema_hashes() {
+    let mut hasher = schema::SchemaHasher::new();
+    hasher.write_bytes(&0x0123_4567_89ab_cdef_u64.to_le_bytes());
+    hasher.write_bytes(&0xfd22_3308_06a2_69a6_u64.to_le_bytes());
+    assert_eq!(hasher.finish(), discrimin_ant::combine_schema_hashes(&[0x0123_4567_89ab_cdef, 0xfd22_3308_06a2_69a6]));
+    assert_eq!(schema::SchemaHasher::new().finish(), discrimin_ant::combine_schema_hashes(&[]));
+}
+
+/// Builds the export of an enum the same way the export options do.
+///
+/// * `attr`: The arguments to the attribute.
+/// * `tokens`: The enum.
+fn export_enum(attr: TokenStream, tokens: TokenStream) -> export::ExportEnum {
+    #[allow(clippy::unwrap_used)]
+    let options = DiscriminantOptions::parse(attr).unwrap();
+    #[allow(clippy::unwrap_used)]
+    let mut item: DeriveInput = syn::parse2(tokens).unwrap();
+    #[allow(clippy::unwrap_used)]
+    let repr = Repr::resolve(options.repr.as_ref(), &mut item.attrs).unwrap();
+    let Data::Enum(e) = &item.data else { unreachable!() };
+    let variants = e.variants.iter().cloned().collect::<Vec<_>>();
+    #[allow(clippy::unwrap_used)]
+    let (discriminants, _) = extract_discriminants_for_repr(&repr.primitive, &item.ident, &variants, false).unwrap();
+    #[allow(clippy::unwrap_used)]
+    export::ExportEnum::new(&item.ident, &repr.primitive, &discriminants, "test").unwrap()
+}
+
+/// Writes the exports of an enum to `out_dir` the same way the macro does, returning the error
+/// message if it fails.
+///
+/// * `out_dir`: The directory standing in for `OUT_DIR`.
+/// * `source`: The file the enum is declared in, relative to this crate's manifest.
+/// * `attr`: The arguments to the attribute.
+/// * `tokens`: The enum.
+fn write_exports(out_dir: &std::path::Path, source: &str, attr: TokenStream, tokens: TokenStream) -> Result<(), String> {
+    #[allow(clippy::unwrap_used)]
+    let options = DiscriminantOptions::parse(attr).unwrap();
+    #[allow(clippy::unwrap_used)]
+    let mut item: DeriveInput = syn::parse2(tokens).unwrap();
+    #[allow(clippy::unwrap_used)]
+    let repr = Repr::resolve(options.repr.as_ref(), &mut item.attrs).unwrap();
+    let Data::Enum(e) = &item.data else { unreachable!() };
+    let variants = e.variants.iter().cloned().collect::<Vec<_>>();
+    #[allow(clippy::unwrap_used)]
+    let (discriminants, _) = extract_discriminants_for_repr(&repr.primitive, &item.ident, &variants, false).unwrap();
+    export::write_exports_to(Some(out_dir), Some(source), &item.ident, &repr.primitive, &discriminants, &options.export).map_err(|error| error.to_string())
+}
+
+#[test]
+fn c_header() {
+    let export = export_enum(quote! { i8 }, quote! { enum Message { Ping = -128, Data(u32, Vec<&'static str>), Move { x: i32, y: i32 } = 5 } });
+    let expected = "/* Generated by discrimin-ant from the Rust enum `Message`. Do not edit. */
+#ifndef DISCRIMIN_ANT_MESSAGE_H
+#define DISCRIMIN_ANT_MESSAGE_H
+
+#ifdef __cplusplus
+#include <cstdint>
+
+enum class Message : std::int8_t {
+    /* Rust: Ping */
+    Ping = (-127 - 1),
+    /* Rust: Data(u32, Vec<&'static str>) */
+    Data = -127,
+    /* Rust: Move { x: i32, y: i32 } */
+    Move = 5,
+};
+
+static_assert(sizeof(Message) == sizeof(std::int8_t), \"Message must have the size of std::int8_t\");
+static_assert(static_cast<std::int8_t>(Message::Ping) == (-127 - 1), \"Message::Ping must be -128\");
+static_assert(static_cast<std::int8_t>(Message::Data) == -127, \"Message::Data must be -127\");
+static_assert(static_cast<std::int8_t>(Message::Move) == 5, \"Message::Move must be 5\");
+#else
+#include <stdint.h>
+
+/* The type that holds a Message on the wire. */
+typedef int8_t Message_repr;
+
+typedef enum Message {
+    /* Rust: Ping */
+    Message_Ping = (-127 - 1),
+    /* Rust: Data(u32, Vec<&'static str>) */
+    Message_Data = -127,
+    /* Rust: Move { x: i32, y: i32 } */
+    Message_Move = 5,
+} Message;
+
+_Static_assert(Message_Ping == (-127 - 1), \"Message_Ping must be -128\");
+_Static_assert(Message_Data == -127, \"Message_Data must be -127\");
+_Static_assert(Message_Move == 5, \"Message_Move must be 5\");
+#endif
+
+#endif /* DISCRIMIN_ANT_MESSAGE_H */
+";
+    assert_eq!(export::render_c_header(&export).as_deref(), Ok(expected));
+
+    let export = export_enum(quote! { u128 }, quote! { enum Message { Ping } });
+    assert_eq!(export::render_c_header(&export).err().as_deref(), Some("`u128` has no C equivalent. Use a repr of at most 64 bits"));
+
+    let result = discriminant_impl(quote! { u8, c_header }, quote! { enum Message { Ping } });
+    let expected = quote! { ::core::compile_error! { "The `c_header` option writes to `OUT_DIR`, which Cargo only sets for packages with a build script. Add a `build.rs`, even one with an empty `fn main() {}`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn c_header_collision() {
+    // Every enum here is declared in this file, so only `src/net.rs` is another enum.
+    let out_dir = std::env::temp_dir().join(format!("discrimin-ant-c_header-{}", std::process::id()));
+    let this_file = "src/discriminant/tests.rs";
+    assert_eq!(write_exports(&out_dir, this_file, quote! { u8, c_header }, quote! { enum Message { Ping } }), Ok(()));
+    #[allow(clippy::unwrap_used)]
+    let header = std::fs::read_to_string(out_dir.join("discrimin-ant/Message.h")).unwrap();
+    assert!(header.starts_with("/* Generated by discrimin-ant from the Rust enum `Message` in `src/discriminant/tests.rs`. Do not edit. */\n"));
+
+    // Editing the enum, e.g. while rust-analyzer keeps the macro loaded, rewrites its file.
+    assert_eq!(write_exports(&out_dir, this_file, quote! { u8, c_header }, quote! { enum Message { Ping, Pong } }), Ok(()));
+
+    let expected = "`Message.h` is already exported by the enum `Message` in `src/discriminant/tests.rs`. Give one of them its own file name with `export_name = \"..\"`";
+    assert_eq!(write_exports(&out_dir, "src/net.rs", quote! { u8, c_header }, quote! { enum Message { Ping } }).err().as_deref(), Some(expected));
+    assert_eq!(write_exports(&out_dir, "src/net.rs", quote! { u8, c_header, export_name = "net_message" }, quote! { enum Message { Ping } }), Ok(()));
+    assert!(out_dir.join("discrimin-ant/net_message.h").is_file());
+
+    // A file exported by an enum that has since moved to another file is replaced.
+    assert_eq!(write_exports(&out_dir, this_file, quote! { u8, c_header, export_name = "net_message" }, quote! { enum Message { Pong } }), Ok(()));
+    let _ = std::fs::remove_dir_all(out_dir);
+}
+
+#[test]
+fn proto() {
+    let export = export_enum(quote! { u8 }, quote! { enum ChatEvent { Joined(u32) = 1, Unspecified = 0, HTTPRequest { url: String } = 5 } });
+    let expected = "// Generated by discrimin-ant from the Rust enum `ChatEvent`. Do not edit.
+syntax = \"proto3\";
+
+package chat.v1;
+
+enum ChatEvent {
+  // Rust: Unspecified
+  CHAT_EVENT_UNSPECIFIED = 0;
+  // Rust: Joined(u32)
+  CHAT_EVENT_JOINED = 1;
+  // Rust: HTTPRequest { url: String }
+  CHAT_EVENT_HTTP_REQUEST = 5;
+}
+";
+    assert_eq!(export::render_proto(&export, Some("chat.v1")).as_deref(), Ok(expected));
+
+    let export = export_enum(quote! { u8 }, quote! { enum ChatEvent { Joined = 1 } });
+    let expected = "proto3 requires `ChatEvent` to have a variant with the discriminant `0`. Add one, such as `Unspecified = 0`";
+    assert_eq!(export::render_proto(&export, None).err().as_deref(), Some(expected));
+
+    let export = export_enum(quote! { u64 }, quote! { enum ChatEvent { Unspecified, Huge = 2_147_483_648 } });
+    let expected = "`Huge` has the discriminant `2147483648`, which does not fit in the `int32` that protobuf uses for enums";
+    assert_eq!(export::render_proto(&export, None).err().as_deref(), Some(expected));
+
+    let export = export_enum(quote! { i64 }, quote! { enum ChatEvent { Unspecified, Low = -2_147_483_648 } });
+    assert!(export::render_proto(&export, None).is_ok());
+
+    let export = export_enum(quote! { u8 }, quote! { enum ChatEvent { Unspecified, FooBar, Foo_Bar } });
+    let expected = "`Foo_Bar` and `FooBar` both produce the protobuf value `CHAT_EVENT_FOO_BAR`";
+    assert_eq!(export::render_proto(&export, None).err().as_deref(), Some(expected));
+}
+
+#[test]
+fn proto_collision() {
+    let out_dir = std::env::temp_dir().join(format!("discrimin-ant-proto-{}", std::process::id()));
+    let this_file = "src/discriminant/tests.rs";
+    assert_eq!(write_exports(&out_dir, this_file, quote! { u8, proto }, quote! { enum Status { Unknown = 0 } }), Ok(()));
+    assert!(out_dir.join("discrimin-ant/Status.proto").is_file());
+    assert_eq!(write_exports(&out_dir, this_file, quote! { u8, proto(package = "chat.v1") }, quote! { enum Status { Unknown = 0 } }), Ok(()));
+
+    let expected = "`Status.proto` is already exported by the enum `Status` in `src/discriminant/tests.rs`. Give one of them its own file name with `export_name = \"..\"`";
+    assert_eq!(write_exports(&out_dir, "src/chat.rs", quote! { u8, proto }, quote! { enum Status { Unknown = 0 } }).err().as_deref(), Some(expected));
+    assert_eq!(write_exports(&out_dir, "src/chat.rs", quote! { u8, proto, export_name = "chat_status" }, quote! { enum Status { Unknown = 0 } }), Ok(()));
+    assert!(out_dir.join("discrimin-ant/chat_status.proto").is_file());
+    let _ = std::fs::remove_dir_all(out_dir);
+}
+
+#[test]
+fn typescript() {
+    let export = export_enum(quote! { i64 }, quote! { enum Frame { Ping = -1, Data(u32, String), Move { x: i32 } = 9_007_199_254_740_991 } });
+    let expected = "// Generated by discrimin-ant from the Rust enum `Frame`. Do not edit.
+
+export const enum Frame {
+  /** Rust: Ping */
+  Ping = -1,
+  /** Rust: Data(u32, String) */
+  Data = 0,
+  /** Rust: Move { x: i32 } */
+  Move = 9007199254740991,
+}
+";
+    assert_eq!(export::render_typescript(&export).as_deref(), Ok(expected));
+
+    let export = export_enum(quote! { u64 }, quote! { enum Frame { Huge = 9_007_199_254_740_992 } });
+    let expected = "`Huge` has the discriminant `9007199254740992`, which a TypeScript `number` cannot hold exactly";
+    assert_eq!(export::render_typescript(&export).err().as_deref(), Some(expected));
+
+    let export = export_enum(quote! { u8 }, quote! { enum r#Frame { r#async, r#yield } });
+    assert_eq!(export.name, "Frame");
+    #[allow(clippy::unwrap_used)]
+    let typescript = export::render_typescript(&export).unwrap();
+    assert!(typescript.contains("export const enum Frame {\n  /** Rust: async */\n  async = 0,\n  /** Rust: yield */\n  yield = 1,\n}"), "{typescript}");
+}
+
+#[test]
+fn python() {
+    let export = export_enum(quote! { u64 }, quote! { enum Frame { Ping = 1, Data(u32, String), Huge { x: i32 } = 18_000_000_000_000_000_000 } });
+    let expected = "# Generated by discrimin-ant from the Rust enum `Frame`. Do not edit.
+from enum import IntEnum
+
+
+class Frame(IntEnum):
+    # Rust: Ping
+    Ping = 1
+    # Rust: Data(u32, String)
+    Data = 2
+    # Rust: Huge { x: i32 }
+    Huge = 18000000000000000000
+";
+    assert_eq!(export::render_python(&export).as_deref(), Ok(expected));
+
+    let export = export_enum(quote! { u8 }, quote! { enum Frame { Some, None } });
+    let expected = "`None` is a keyword in Python, so it cannot name a member of `Frame`";
+    assert_eq!(export::render_python(&export).err().as_deref(), Some(expected));
+
+    let export = export_enum(quote! { u8 }, quote! { enum Frame { Ping, r#yield } });
+    let expected = "`yield` is a keyword in Python, so it cannot name a member of `Frame`";
+    assert_eq!(export::render_python(&export).err().as_deref(), Some(expected));
+}
+
+#[test]
+fn typescript_and_python_collision() {
+    let out_dir = std::env::temp_dir().join(format!("discrimin-ant-typescript-python-{}", std::process::id()));
+    let this_file = "src/discriminant/tests.rs";
+    assert_eq!(write_exports(&out_dir, this_file, quote! { u8, typescript, python }, quote! { enum ChatEvent { Joined } }), Ok(()));
+    assert!(out_dir.join("discrimin-ant/ChatEvent.ts").is_file());
+    assert!(out_dir.join("discrimin-ant/chat_event.py").is_file());
+    assert_eq!(write_exports(&out_dir, this_file, quote! { u8, typescript, python }, quote! { enum ChatEvent { Joined, Left } }), Ok(()));
+
+    let expected = "`ChatEvent.ts` is already exported by the enum `ChatEvent` in `src/discriminant/tests.rs`. Give one of them its own file name with `export_name = \"..\"`";
+    assert_eq!(write_exports(&out_dir, "src/room.rs", quote! { u8, typescript }, quote! { enum ChatEvent { Left } }).err().as_deref(), Some(expected));
+    let expected = "`chat_event.py` is already exported by the enum `ChatEvent` in `src/discriminant/tests.rs`. Give one of them its own file name with `export_name = \"..\"`";
+    assert_eq!(write_exports(&out_dir, "src/room.rs", quote! { u8, python }, quote! { enum ChatEvent { Left } }).err().as_deref(), Some(expected));
+
+    assert_eq!(
+        write_exports(&out_dir, "src/room.rs", quote! { u8, typescript, python, export_name = "room_event" }, quote! { enum ChatEvent { Left } }),
+        Ok(())
+    );
+    assert!(out_dir.join("discrimin-ant/room_event.ts").is_file());
+    assert!(out_dir.join("discrimin-ant/room_event.py").is_file());
+    let _ = std::fs::remove_dir_all(out_dir);
+}
+
+#[test]
+fn schemars_requires_serde() {
+    let result = discriminant_impl(quote! { u8, schemars }, quote! { enum SimpleEnum { A } });
+    let expected = quote! { ::core::compile_error! { "The `schemars` option describes the encoding of the `serde` option. Add `serde`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn codec_requires_fieldless() {
+    let result = discriminant_impl(quote! { u8, codec, fieldless(skip) }, quote! { enum SimpleEnum { A } });
+    let expected = quote! { ::core::compile_error! { "The `codec` option requires the fieldless enum. Remove `fieldless(skip)`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn codec_requires_fixed_width_repr() {
+    let result = discriminant_impl(quote! { usize, codec }, quote! { enum SimpleEnum { A } });
+    let expected = quote! { ::core::compile_error! { "The `codec` option needs a repr with the same width on every target, which `usize` does not have. Use a repr such as `u32` or `u64`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+
+    let result = discriminant_impl(quote! { codec }, quote! { #[repr(isize)] enum SimpleEnum { A } });
+    let expected = quote! { ::core::compile_error! { "The `codec` option needs a repr with the same width on every target, which `isize` does not have. Use a repr such as `u32` or `u64`" } };
+    assert_eq!(result.to_string(), expected.to_string());
+}
+
+#[test]
+fn discriminant_enum() {
+    #[allow(clippy::unwrap_used)]
+    let item = syn::parse2(quote! {
+        #[repr(i8)]
+        enum Message {
+            Ping = -1,
+            #[discriminant(retired)]
+            Old,
+            #[discriminant(rename = "data")]
+            Data(u32, Vec<u8>),
+            Move { x: i32 } = 5,
+        }
+    })
+    .unwrap();
+    #[allow(clippy::unwrap_used)]
+    let message = DiscriminantEnum::from_attribute(quote! { rename_all = "SCREAMING_SNAKE_CASE" }, &item).unwrap();
+    assert_eq!(message.ident, "Message");
+    assert_eq!(message.repr, "i8");
+
+    let variants = message.variants.iter().map(|variant| (variant.ident().to_string(), variant.name.as_str(), variant.value, variant.retired)).collect::<Vec<_>>();
+    let expected = [
+        ("Ping".to_string(), "PING", Some(DiscriminantValue::Signed(-1)), false),
+        ("Old".to_string(), "Old", Some(DiscriminantValue::Signed(0)), true),
+        ("Data".to_string(), "data", Some(DiscriminantValue::Signed(1)), false),
+        ("Move".to_string(), "MOVE", Some(DiscriminantValue::Signed(5)), false),
+    ];
+    assert_eq!(variants, expected);
+    assert_eq!(message.variants[2].shape(), FieldShape::Unnamed(vec!["u32".to_string(), "Vec<u8>".to_string()]));
+    assert_eq!(message.variants[3].shape(), FieldShape::Named(vec![("x".to_string(), "i32".to_string())]));
+
+    let current = message.current_variants();
+    assert_eq!(current.iter().map(|variant| variant.ident().to_string()).collect::<Vec<_>>(), ["Ping", "Data", "Move"]);
+    let expected = quote! {
+        if value == (-1) { return Ok(Self::Ping); }
+        if value == (-1 + 2i8) { return Ok(Self::Data); }
+        if value == (5) { return Ok(Self::Move); }
+    };
+    assert_eq!(generate_fieldless_try_from_variants(&current).to_string(), expected.to_string());
+}
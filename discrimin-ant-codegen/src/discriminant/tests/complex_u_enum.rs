@@ -0,0 +1,129 @@
+#[repr(u8)]
+pub enum ComplexUEnum {
+    One(i32) = 1,
+    Two(i32),
+    Five { x: u32 } = 5,
+    Six { x: u32 },
+}
+const _: Option<&str> = option_env!("DISCRIMIN_ANT_EXPLICIT");
+impl ComplexUEnum {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [ComplexUEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0xac5e_b081_0175_52e3;
+
+    #[doc = r" Returns the discriminant of [Self]."]
+    pub const fn discriminant(&self) -> u8 {
+        unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
+    }
+}
+impl discrimin_ant::Discriminantable for ComplexUEnum {
+    type Discriminant = u8;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+    fn discriminant(&self) -> Self::Discriminant {
+        self.discriminant()
+    }
+}
+#[doc = "Fieldless representations of [ComplexUEnum]. Used to extract discriminants without fully constructing the enum."]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum ComplexUEnum_ {
+    #[doc = "A fieldless version of [ComplexUEnum::One], used to extract the variant's discriminant without needing to fully construct it."]
+    One = 1,
+    #[doc = "A fieldless version of [ComplexUEnum::Two], used to extract the variant's discriminant without needing to fully construct it."]
+    Two = 1 + 1u8,
+    #[doc = "A fieldless version of [ComplexUEnum::Five], used to extract the variant's discriminant without needing to fully construct it."]
+    Five = 5,
+    #[doc = "A fieldless version of [ComplexUEnum::Six], used to extract the variant's discriminant without needing to fully construct it."]
+    Six = 5 + 1u8,
+}
+impl ComplexUEnum_ {
+    #[doc = r" Every variant of [Self], in declaration order."]
+    pub const VARIANTS: [Self; 4] = [Self::One, Self::Two, Self::Five, Self::Six];
+    #[doc = r" The number of variants of [Self]."]
+    pub const COUNT: usize = 4;
+    #[doc = r" The discriminant of every variant of [Self], in declaration order."]
+    pub const DISCRIMINANTS: [u8; 4] = [Self::One as u8, Self::Two as u8, Self::Five as u8, Self::Six as u8];
+
+    #[doc = r" Returns the discriminant of [Self]."]
+    pub const fn discriminant(&self) -> u8 {
+        unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
+    }
+
+    #[doc = r" Returns an iterator over every variant of [Self], in declaration order."]
+    pub fn iter() -> core::array::IntoIter<Self, 4> {
+        Self::VARIANTS.into_iter()
+    }
+
+    #[doc = r" Returns the name of the variant."]
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            Self::One => "One",
+            Self::Two => "Two",
+            Self::Five => "Five",
+            Self::Six => "Six",
+        }
+    }
+}
+impl core::fmt::Display for ComplexUEnum_ {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+impl core::str::FromStr for ComplexUEnum_ {
+    type Err = discrimin_ant::ParseVariantError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "One" => Ok(Self::One),
+            "Two" => Ok(Self::Two),
+            "Five" => Ok(Self::Five),
+            "Six" => Ok(Self::Six),
+            _ => s
+                .parse::<u8>()
+                .ok()
+                .and_then(|discriminant| Self::try_from(discriminant).ok())
+                .ok_or_else(|| discrimin_ant::ParseVariantError::new(s, stringify!(ComplexUEnum_))),
+        }
+    }
+}
+impl TryFrom<u8> for ComplexUEnum_ {
+    type Error = discrimin_ant::UnknownDiscriminant<Self, u8>;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value == (1) {
+            return Ok(Self::One);
+        }
+        if value == (1 + 1u8) {
+            return Ok(Self::Two);
+        }
+        if value == (5) {
+            return Ok(Self::Five);
+        }
+        if value == (5 + 1u8) {
+            return Ok(Self::Six);
+        }
+        Err(discrimin_ant::UnknownDiscriminant::new(value, stringify!(ComplexUEnum_)))
+    }
+}
+impl From<&ComplexUEnum> for ComplexUEnum_ {
+    fn from(value: &ComplexUEnum) -> Self {
+        match value {
+            ComplexUEnum::One(..) => Self::One,
+            ComplexUEnum::Two(..) => Self::Two,
+            ComplexUEnum::Five { .. } => Self::Five,
+            ComplexUEnum::Six { .. } => Self::Six,
+        }
+    }
+}
+impl ComplexUEnum_ {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [ComplexUEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0xac5e_b081_0175_52e3;
+}
+impl discrimin_ant::Discriminantable for ComplexUEnum_ {
+    type Discriminant = u8;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+    fn discriminant(&self) -> Self::Discriminant {
+        self.discriminant()
+    }
+}
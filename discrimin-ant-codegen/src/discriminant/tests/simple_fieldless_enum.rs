@@ -5,7 +5,11 @@ pub enum SimpleFieldlessEnum {
     Five = 5,
     Six,
 }
+const _: Option<&str> = option_env!("DISCRIMIN_ANT_EXPLICIT");
 impl SimpleFieldlessEnum {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [SimpleFieldlessEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0xfd22_3308_06a2_69a6;
+
     #[doc = r" Returns the discriminant of [Self]."]
     pub const fn discriminant(&self) -> u8 {
         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
@@ -13,12 +17,14 @@ impl SimpleFieldlessEnum {
 }
 impl discrimin_ant::Discriminantable for SimpleFieldlessEnum {
     type Discriminant = u8;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
 
     fn discriminant(&self) -> Self::Discriminant {
         self.discriminant()
     }
 }
 #[doc = "Fieldless representations of [SimpleFieldlessEnum]. Used to extract discriminants without fully constructing the enum."]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum SimpleFieldlessEnum_ {
     #[doc = "A fieldless version of [SimpleFieldlessEnum::One], used to extract the variant's discriminant without needing to fully construct it."]
@@ -31,20 +37,58 @@ pub enum SimpleFieldlessEnum_ {
     Six = 5 + 1u8,
 }
 impl SimpleFieldlessEnum_ {
+    #[doc = r" Every variant of [Self], in declaration order."]
+    pub const VARIANTS: [Self; 4] = [Self::One, Self::Two, Self::Five, Self::Six];
+    #[doc = r" The number of variants of [Self]."]
+    pub const COUNT: usize = 4;
+    #[doc = r" The discriminant of every variant of [Self], in declaration order."]
+    pub const DISCRIMINANTS: [u8; 4] = [Self::One as u8, Self::Two as u8, Self::Five as u8, Self::Six as u8];
+
     #[doc = r" Returns the discriminant of [Self]."]
     pub const fn discriminant(&self) -> u8 {
         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
     }
+
+    #[doc = r" Returns an iterator over every variant of [Self], in declaration order."]
+    pub fn iter() -> core::array::IntoIter<Self, 4> {
+        Self::VARIANTS.into_iter()
+    }
+
+    #[doc = r" Returns the name of the variant."]
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            Self::One => "One",
+            Self::Two => "Two",
+            Self::Five => "Five",
+            Self::Six => "Six",
+        }
+    }
 }
-impl discrimin_ant::Discriminantable for SimpleFieldlessEnum_ {
-    type Discriminant = u8;
+impl core::fmt::Display for SimpleFieldlessEnum_ {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+impl core::str::FromStr for SimpleFieldlessEnum_ {
+    type Err = discrimin_ant::ParseVariantError;
 
-    fn discriminant(&self) -> Self::Discriminant {
-        self.discriminant()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "One" => Ok(Self::One),
+            "Two" => Ok(Self::Two),
+            "Five" => Ok(Self::Five),
+            "Six" => Ok(Self::Six),
+            _ => s
+                .parse::<u8>()
+                .ok()
+                .and_then(|discriminant| Self::try_from(discriminant).ok())
+                .ok_or_else(|| discrimin_ant::ParseVariantError::new(s, stringify!(SimpleFieldlessEnum_))),
+        }
     }
 }
 impl TryFrom<u8> for SimpleFieldlessEnum_ {
-    type Error = ();
+    type Error = discrimin_ant::UnknownDiscriminant<Self, u8>;
+
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         if value == (1) {
             return Ok(Self::One);
@@ -58,7 +102,7 @@ impl TryFrom<u8> for SimpleFieldlessEnum_ {
         if value == (5 + 1u8) {
             return Ok(Self::Six);
         }
-        Err(())
+        Err(discrimin_ant::UnknownDiscriminant::new(value, stringify!(SimpleFieldlessEnum_)))
     }
 }
 impl From<&SimpleFieldlessEnum> for SimpleFieldlessEnum_ {
@@ -71,3 +115,15 @@ impl From<&SimpleFieldlessEnum> for SimpleFieldlessEnum_ {
         }
     }
 }
+impl SimpleFieldlessEnum_ {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [SimpleFieldlessEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0xfd22_3308_06a2_69a6;
+}
+impl discrimin_ant::Discriminantable for SimpleFieldlessEnum_ {
+    type Discriminant = u8;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+    fn discriminant(&self) -> Self::Discriminant {
+        self.discriminant()
+    }
+}
@@ -0,0 +1,82 @@
+#[doc = "An open version of [SimpleFieldlessEnum] that can hold any `u8`, with a constant for the discriminant of each variant."]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct SimpleFieldlessEnumCode(pub u8);
+impl SimpleFieldlessEnumCode {
+    #[doc = "The schema hash of [SimpleFieldlessEnum], for checking that peers agree on it."]
+    pub const SCHEMA_HASH: u64 = 0xfd22_3308_06a2_69a6;
+
+    #[doc = "The discriminant of [SimpleFieldlessEnum::One]."]
+    pub const ONE: Self = Self(1);
+    #[doc = "The discriminant of [SimpleFieldlessEnum::Two]."]
+    pub const TWO: Self = Self(1 + 1u8);
+    #[doc = "The discriminant of [SimpleFieldlessEnum::Five]."]
+    pub const FIVE: Self = Self(5);
+    #[doc = "The discriminant of [SimpleFieldlessEnum::Six]."]
+    pub const SIX: Self = Self(5 + 1u8);
+
+    #[doc = r" Returns the discriminant held by [Self]."]
+    pub const fn discriminant(self) -> u8 {
+        self.0
+    }
+
+    #[doc = r" Returns the name of the variant with this discriminant, or `None` if no variant has it."]
+    pub const fn name(self) -> Option<&'static str> {
+        if self.0 == Self::ONE.0 {
+            return Some("One");
+        }
+        if self.0 == Self::TWO.0 {
+            return Some("Two");
+        }
+        if self.0 == Self::FIVE.0 {
+            return Some("Five");
+        }
+        if self.0 == Self::SIX.0 {
+            return Some("Six");
+        }
+        None
+    }
+}
+impl core::fmt::Debug for SimpleFieldlessEnumCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => f.debug_tuple(stringify!(SimpleFieldlessEnumCode)).field(&self.0).finish(),
+        }
+    }
+}
+impl discrimin_ant::Discriminantable for SimpleFieldlessEnumCode {
+    type Discriminant = u8;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+    fn discriminant(&self) -> Self::Discriminant {
+        self.0
+    }
+}
+impl From<u8> for SimpleFieldlessEnumCode {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+impl From<SimpleFieldlessEnumCode> for u8 {
+    fn from(value: SimpleFieldlessEnumCode) -> Self {
+        value.0
+    }
+}
+impl From<SimpleFieldlessEnum_> for SimpleFieldlessEnumCode {
+    fn from(value: SimpleFieldlessEnum_) -> Self {
+        Self(value.discriminant())
+    }
+}
+impl TryFrom<SimpleFieldlessEnumCode> for SimpleFieldlessEnum_ {
+    type Error = <Self as TryFrom<u8>>::Error;
+
+    fn try_from(value: SimpleFieldlessEnumCode) -> Result<Self, Self::Error> {
+        Self::try_from(value.0)
+    }
+}
+impl From<&SimpleFieldlessEnum> for SimpleFieldlessEnumCode {
+    fn from(value: &SimpleFieldlessEnum) -> Self {
+        Self(value.discriminant())
+    }
+}
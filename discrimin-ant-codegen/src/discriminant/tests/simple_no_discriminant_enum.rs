@@ -7,7 +7,11 @@ pub enum SimpleNoDiscriminantEnum {
     Four,
     Five,
 }
+const _: Option<&str> = option_env!("DISCRIMIN_ANT_EXPLICIT");
 impl SimpleNoDiscriminantEnum {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [SimpleNoDiscriminantEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0x0236_f3c5_9118_ce13;
+
     #[doc = r" Returns the discriminant of [Self]."]
     pub const fn discriminant(&self) -> u16 {
         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u16>() }
@@ -15,12 +19,14 @@ impl SimpleNoDiscriminantEnum {
 }
 impl discrimin_ant::Discriminantable for SimpleNoDiscriminantEnum {
     type Discriminant = u16;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
 
     fn discriminant(&self) -> Self::Discriminant {
         self.discriminant()
     }
 }
 #[doc = "Fieldless representations of [SimpleNoDiscriminantEnum]. Used to extract discriminants without fully constructing the enum."]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum SimpleNoDiscriminantEnum_ {
     #[doc = "A fieldless version of [SimpleNoDiscriminantEnum::Zero], used to extract the variant's discriminant without needing to fully construct it."]
@@ -37,20 +43,62 @@ pub enum SimpleNoDiscriminantEnum_ {
     Five = 3 + 2u16,
 }
 impl SimpleNoDiscriminantEnum_ {
+    #[doc = r" Every variant of [Self], in declaration order."]
+    pub const VARIANTS: [Self; 6] = [Self::Zero, Self::One, Self::Two, Self::Three, Self::Four, Self::Five];
+    #[doc = r" The number of variants of [Self]."]
+    pub const COUNT: usize = 6;
+    #[doc = r" The discriminant of every variant of [Self], in declaration order."]
+    pub const DISCRIMINANTS: [u16; 6] = [Self::Zero as u16, Self::One as u16, Self::Two as u16, Self::Three as u16, Self::Four as u16, Self::Five as u16];
+
     #[doc = r" Returns the discriminant of [Self]."]
     pub const fn discriminant(&self) -> u16 {
         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u16>() }
     }
+
+    #[doc = r" Returns an iterator over every variant of [Self], in declaration order."]
+    pub fn iter() -> core::array::IntoIter<Self, 6> {
+        Self::VARIANTS.into_iter()
+    }
+
+    #[doc = r" Returns the name of the variant."]
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            Self::Zero => "Zero",
+            Self::One => "One",
+            Self::Two => "Two",
+            Self::Three => "Three",
+            Self::Four => "Four",
+            Self::Five => "Five",
+        }
+    }
 }
-impl discrimin_ant::Discriminantable for SimpleNoDiscriminantEnum_ {
-    type Discriminant = u16;
+impl core::fmt::Display for SimpleNoDiscriminantEnum_ {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+impl core::str::FromStr for SimpleNoDiscriminantEnum_ {
+    type Err = discrimin_ant::ParseVariantError;
 
-    fn discriminant(&self) -> Self::Discriminant {
-        self.discriminant()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Zero" => Ok(Self::Zero),
+            "One" => Ok(Self::One),
+            "Two" => Ok(Self::Two),
+            "Three" => Ok(Self::Three),
+            "Four" => Ok(Self::Four),
+            "Five" => Ok(Self::Five),
+            _ => s
+                .parse::<u16>()
+                .ok()
+                .and_then(|discriminant| Self::try_from(discriminant).ok())
+                .ok_or_else(|| discrimin_ant::ParseVariantError::new(s, stringify!(SimpleNoDiscriminantEnum_))),
+        }
     }
 }
 impl TryFrom<u16> for SimpleNoDiscriminantEnum_ {
-    type Error = ();
+    type Error = discrimin_ant::UnknownDiscriminant<Self, u16>;
+
     fn try_from(value: u16) -> Result<Self, Self::Error> {
         if value == (0u16) {
             return Ok(Self::Zero);
@@ -70,7 +118,7 @@ impl TryFrom<u16> for SimpleNoDiscriminantEnum_ {
         if value == (3 + 2u16) {
             return Ok(Self::Five);
         }
-        Err(())
+        Err(discrimin_ant::UnknownDiscriminant::new(value, stringify!(SimpleNoDiscriminantEnum_)))
     }
 }
 impl From<&SimpleNoDiscriminantEnum> for SimpleNoDiscriminantEnum_ {
@@ -85,3 +133,15 @@ impl From<&SimpleNoDiscriminantEnum> for SimpleNoDiscriminantEnum_ {
         }
     }
 }
+impl SimpleNoDiscriminantEnum_ {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [SimpleNoDiscriminantEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0x0236_f3c5_9118_ce13;
+}
+impl discrimin_ant::Discriminantable for SimpleNoDiscriminantEnum_ {
+    type Discriminant = u16;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+    fn discriminant(&self) -> Self::Discriminant {
+        self.discriminant()
+    }
+}
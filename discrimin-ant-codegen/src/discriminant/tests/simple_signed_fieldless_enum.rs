@@ -7,7 +7,11 @@ pub enum SimpleSignedFieldlessEnum {
     Six,
     Seven,
 }
+const _: Option<&str> = option_env!("DISCRIMIN_ANT_EXPLICIT");
 impl SimpleSignedFieldlessEnum {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [SimpleSignedFieldlessEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0x9a41_21e8_1ccf_2ca6;
+
     #[doc = r" Returns the discriminant of [Self]."]
     pub const fn discriminant(&self) -> i8 {
         unsafe { *core::ptr::from_ref::<Self>(self).cast::<i8>() }
@@ -15,12 +19,14 @@ impl SimpleSignedFieldlessEnum {
 }
 impl discrimin_ant::Discriminantable for SimpleSignedFieldlessEnum {
     type Discriminant = i8;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
 
     fn discriminant(&self) -> Self::Discriminant {
         self.discriminant()
     }
 }
 #[doc = "Fieldless representations of [SimpleSignedFieldlessEnum]. Used to extract discriminants without fully constructing the enum."]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(i8)]
 pub enum SimpleSignedFieldlessEnum_ {
     #[doc = "A fieldless version of [SimpleSignedFieldlessEnum::NOne], used to extract the variant's discriminant without needing to fully construct it."]
@@ -37,20 +43,62 @@ pub enum SimpleSignedFieldlessEnum_ {
     Seven = 5 + 2i8,
 }
 impl SimpleSignedFieldlessEnum_ {
+    #[doc = r" Every variant of [Self], in declaration order."]
+    pub const VARIANTS: [Self; 6] = [Self::NOne, Self::One, Self::Two, Self::Five, Self::Six, Self::Seven];
+    #[doc = r" The number of variants of [Self]."]
+    pub const COUNT: usize = 6;
+    #[doc = r" The discriminant of every variant of [Self], in declaration order."]
+    pub const DISCRIMINANTS: [i8; 6] = [Self::NOne as i8, Self::One as i8, Self::Two as i8, Self::Five as i8, Self::Six as i8, Self::Seven as i8];
+
     #[doc = r" Returns the discriminant of [Self]."]
     pub const fn discriminant(&self) -> i8 {
         unsafe { *core::ptr::from_ref::<Self>(self).cast::<i8>() }
     }
+
+    #[doc = r" Returns an iterator over every variant of [Self], in declaration order."]
+    pub fn iter() -> core::array::IntoIter<Self, 6> {
+        Self::VARIANTS.into_iter()
+    }
+
+    #[doc = r" Returns the name of the variant."]
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            Self::NOne => "NOne",
+            Self::One => "One",
+            Self::Two => "Two",
+            Self::Five => "Five",
+            Self::Six => "Six",
+            Self::Seven => "Seven",
+        }
+    }
 }
-impl discrimin_ant::Discriminantable for SimpleSignedFieldlessEnum_ {
-    type Discriminant = i8;
+impl core::fmt::Display for SimpleSignedFieldlessEnum_ {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+impl core::str::FromStr for SimpleSignedFieldlessEnum_ {
+    type Err = discrimin_ant::ParseVariantError;
 
-    fn discriminant(&self) -> Self::Discriminant {
-        self.discriminant()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NOne" => Ok(Self::NOne),
+            "One" => Ok(Self::One),
+            "Two" => Ok(Self::Two),
+            "Five" => Ok(Self::Five),
+            "Six" => Ok(Self::Six),
+            "Seven" => Ok(Self::Seven),
+            _ => s
+                .parse::<i8>()
+                .ok()
+                .and_then(|discriminant| Self::try_from(discriminant).ok())
+                .ok_or_else(|| discrimin_ant::ParseVariantError::new(s, stringify!(SimpleSignedFieldlessEnum_))),
+        }
     }
 }
 impl TryFrom<i8> for SimpleSignedFieldlessEnum_ {
-    type Error = ();
+    type Error = discrimin_ant::UnknownDiscriminant<Self, i8>;
+
     fn try_from(value: i8) -> Result<Self, Self::Error> {
         if value == (-1) {
             return Ok(Self::NOne);
@@ -70,7 +118,7 @@ impl TryFrom<i8> for SimpleSignedFieldlessEnum_ {
         if value == (5 + 2i8) {
             return Ok(Self::Seven);
         }
-        Err(())
+        Err(discrimin_ant::UnknownDiscriminant::new(value, stringify!(SimpleSignedFieldlessEnum_)))
     }
 }
 impl From<&SimpleSignedFieldlessEnum> for SimpleSignedFieldlessEnum_ {
@@ -85,3 +133,15 @@ impl From<&SimpleSignedFieldlessEnum> for SimpleSignedFieldlessEnum_ {
         }
     }
 }
+impl SimpleSignedFieldlessEnum_ {
+    #[doc = "A hash of the name, repr, variant names and discriminants of [SimpleSignedFieldlessEnum], for checking that peers agree on them."]
+    pub const SCHEMA_HASH: u64 = 0x9a41_21e8_1ccf_2ca6;
+}
+impl discrimin_ant::Discriminantable for SimpleSignedFieldlessEnum_ {
+    type Discriminant = i8;
+    const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+
+    fn discriminant(&self) -> Self::Discriminant {
+        self.discriminant()
+    }
+}
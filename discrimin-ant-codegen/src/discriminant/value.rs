@@ -0,0 +1,189 @@
+//! Evaluates discriminants at macro time so that invalid discriminants can be reported against the
+//! variant that caused them instead of surfacing as confusing errors in the generated code.
+use core::fmt;
+use std::collections::HashMap;
+
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, PrimInt};
+use proc_macro2::{Literal, TokenStream};
+use quote::{ToTokens, quote, quote_spanned};
+use syn::{BinOp, Expr, ExprBinary, ExprLit, ExprUnary, Ident, Lit, UnOp};
+
+use super::DiscriminantVariant;
+
+/// The value of a discriminant, evaluated at macro time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DiscriminantValue {
+    /// The value of a discriminant with a signed repr.
+    Signed(i128),
+    /// The value of a discriminant with an unsigned repr.
+    Unsigned(u128),
+}
+
+impl DiscriminantValue {
+    /// Converts a discriminant of the enum's repr into a [`DiscriminantValue`].
+    ///
+    /// * `value`: The discriminant.
+    pub fn from_int<T: PrimInt>(value: T) -> Self {
+        if T::min_value() < T::zero() {
+            Self::Signed(value.to_i128().unwrap_or_default())
+        } else {
+            Self::Unsigned(value.to_u128().unwrap_or_default())
+        }
+    }
+}
+
+impl fmt::Display for DiscriminantValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(value) => value.fmt(f),
+            Self::Unsigned(value) => value.fmt(f),
+        }
+    }
+}
+
+impl ToTokens for DiscriminantValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match *self {
+            Self::Signed(value) => Literal::i128_unsuffixed(value).to_tokens(tokens),
+            Self::Unsigned(value) => Literal::u128_unsuffixed(value).to_tokens(tokens),
+        }
+    }
+}
+
+/// Evaluates a discriminant expression for the integer type named by `repr`. See
+/// [`fold_discriminant`].
+///
+/// * `expr`: The discriminant expression.
+/// * `repr`: The repr for the enum.
+///
+/// # Errors
+/// Fails if the expression does not fit in the repr or the repr is not a primitive integer type.
+pub fn fold_for_repr(expr: &Expr, repr: &Ident) -> syn::Result<Option<DiscriminantValue>> {
+    match repr.to_string().as_str() {
+        "u8" => fold_discriminant::<u8>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "u16" => fold_discriminant::<u16>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "u32" => fold_discriminant::<u32>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "u64" => fold_discriminant::<u64>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "u128" => fold_discriminant::<u128>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "usize" => fold_discriminant::<usize>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "i8" => fold_discriminant::<i8>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "i16" => fold_discriminant::<i16>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "i32" => fold_discriminant::<i32>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "i64" => fold_discriminant::<i64>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "i128" => fold_discriminant::<i128>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        "isize" => fold_discriminant::<isize>(expr).map(|value| value.map(DiscriminantValue::from_int)),
+        _ => Err(syn::Error::new(repr.span(), format!("Unsupported discriminant type `{repr}`"))),
+    }
+}
+
+/// Evaluates a discriminant expression made up of integer literals, negation, addition,
+/// subtraction and multiplication.
+///
+/// Returns `None` if the expression cannot be evaluated at macro time, such as when it references
+/// a constant.
+///
+/// * `expr`: The discriminant expression.
+///
+/// # Errors
+/// Fails if the expression does not fit in `T`.
+pub fn fold_discriminant<T: PrimInt>(expr: &Expr) -> syn::Result<Option<T>> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(lit), .. }) => parse_int(lit.base10_digits(), expr).map(Some),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr: inner, .. }) => {
+            // Negative literals are parsed directly so that `-128` fits in an `i8`
+            if let Expr::Lit(ExprLit { lit: Lit::Int(lit), .. }) = &**inner {
+                return parse_int(&format!("-{}", lit.base10_digits()), expr).map(Some);
+            }
+            let Some(value) = fold_discriminant::<T>(inner)? else {
+                return Ok(None);
+            };
+            T::zero().checked_sub(&value).map(Some).ok_or_else(|| overflow_error::<T>(expr))
+        }
+        Expr::Binary(ExprBinary { left, op, right, .. }) => {
+            let operation: fn(&T, &T) -> Option<T> = match op {
+                BinOp::Add(_) => CheckedAdd::checked_add,
+                BinOp::Sub(_) => CheckedSub::checked_sub,
+                BinOp::Mul(_) => CheckedMul::checked_mul,
+                _ => return Ok(None),
+            };
+            let (Some(left), Some(right)) = (fold_discriminant::<T>(left)?, fold_discriminant::<T>(right)?) else {
+                return Ok(None);
+            };
+            operation(&left, &right).map(Some).ok_or_else(|| overflow_error::<T>(expr))
+        }
+        Expr::Paren(paren) => fold_discriminant(&paren.expr),
+        Expr::Group(group) => fold_discriminant(&group.expr),
+        _ => Ok(None),
+    }
+}
+
+/// Checks that no two variants share a discriminant.
+///
+/// Discriminants that were evaluated at macro time are compared directly. Any pair involving a
+/// discriminant that could not be evaluated is checked with a `const` assertion instead, which is
+/// returned.
+///
+/// * `discriminants`: The discriminants of the enum.
+/// * `item_ident`: The name of the enum.
+/// * `repr`: The repr for the enum.
+///
+/// # Errors
+/// Fails if two evaluated discriminants are the same.
+pub fn check_duplicate_discriminants(discriminants: &[DiscriminantVariant], item_ident: &Ident, repr: &Ident) -> syn::Result<TokenStream> {
+    let mut error: Option<syn::Error> = None;
+    let mut seen: HashMap<DiscriminantValue, &Ident> = HashMap::new();
+    for DiscriminantVariant { variant, value, .. } in discriminants {
+        let Some(value) = value else { continue };
+        if let Some(first) = seen.get(value) {
+            let duplicate = syn::Error::new_spanned(
+                &variant.ident,
+                format!("`{}` has the discriminant `{value}`, which is already used by `{first}`", variant.ident),
+            );
+            match &mut error {
+                Some(error) => error.combine(duplicate),
+                None => error = Some(duplicate),
+            }
+        } else {
+            seen.insert(*value, &variant.ident);
+        }
+    }
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    let mut assertions = quote! {};
+    for (i, first) in discriminants.iter().enumerate() {
+        for second in discriminants.iter().skip(i.saturating_add(1)) {
+            if first.value.is_some() && second.value.is_some() {
+                continue;
+            }
+            let (first_expr, second_expr) = (&first.expr, &second.expr);
+            let message = format!("`{}` and `{}` of `{item_ident}` have the same discriminant", first.variant.ident, second.variant.ident);
+            let span = second.variant.ident.span();
+            assertions = quote_spanned! {span=>
+                #assertions
+                const _: () = {
+                    let first: #repr = #first_expr;
+                    let second: #repr = #second_expr;
+                    assert!(first != second, #message);
+                };
+            };
+        }
+    }
+    Ok(assertions)
+}
+
+/// Parses a base 10 integer into the repr.
+///
+/// * `digits`: The digits of the integer, optionally preceded by a `-`.
+/// * `expr`: The expression the integer came from, used for errors.
+fn parse_int<T: PrimInt>(digits: &str, expr: &Expr) -> syn::Result<T> {
+    T::from_str_radix(digits, 10).map_err(|_| syn::Error::new_spanned(expr, format!("The discriminant `{digits}` does not fit in `{}`", core::any::type_name::<T>())))
+}
+
+/// Creates an error for a discriminant expression that overflows the repr.
+///
+/// * `expr`: The expression that overflows.
+fn overflow_error<T>(expr: &Expr) -> syn::Error {
+    syn::Error::new_spanned(expr, format!("The discriminant `{}` overflows `{}`", quote!(#expr), core::any::type_name::<T>()))
+}
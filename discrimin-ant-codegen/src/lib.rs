@@ -0,0 +1,65 @@
+//! # Discrimin-Ant Codegen
+//!
+//! The analysis and code generation behind the `discriminant` attribute and `Discriminant` derive
+//! of `discrimin-ant-proc`. A proc-macro crate can only export macros, so the implementation lives
+//! here where tools can share it, such as the `discrimin-ant` command line tool.
+//!
+//! [`DiscriminantEnum`] resolves the discriminant of every variant exactly as the macros do,
+//! without generating any code:
+//! ```rust
+//! use discrimin_ant_codegen::{DiscriminantEnum, DiscriminantValue, FieldShape};
+//! use quote::quote;
+//!
+//! # fn main() -> syn::Result<()> {
+//! let item = syn::parse2(quote! {
+//!     enum Message {
+//!         Ping = 1,
+//!         Data(u32),
+//!         Move { x: i32, y: i32 } = 5,
+//!     }
+//! })?;
+//! let message = DiscriminantEnum::from_attribute(quote! { u8 }, &item)?;
+//! let data = &message.variants[1];
+//! assert_eq!(data.value, Some(DiscriminantValue::Unsigned(2)));
+//! assert_eq!(data.shape(), FieldShape::Unnamed(vec!["u32".to_string()]));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The pieces of code that the macros generate are also available on their own, so that other
+//! macros can compose them. Each generator takes the current (not retired) variants:
+//! ```rust
+//! use discrimin_ant_codegen::{DiscriminantEnum, generate_fieldless_try_from_variants, generate_fieldless_variants};
+//! use quote::quote;
+//!
+//! # fn main() -> syn::Result<()> {
+//! let item = syn::parse2(quote! { enum Message { Ping = 1, Data(u32) } })?;
+//! let message = DiscriminantEnum::from_attribute(quote! { u8 }, &item)?;
+//! let variants = message.current_variants();
+//! let fieldless_variants = generate_fieldless_variants(&variants, &message.ident, &message.repr, None);
+//! let try_from_variants = generate_fieldless_try_from_variants(&variants);
+//! let kind = quote! {
+//!     #[repr(u8)]
+//!     enum MessageKind {
+//!         #fieldless_variants
+//!     }
+//!
+//!     impl TryFrom<u8> for MessageKind {
+//!         type Error = u8;
+//!
+//!         fn try_from(value: u8) -> Result<Self, Self::Error> {
+//!             #try_from_variants
+//!             Err(value)
+//!         }
+//!     }
+//! };
+//! assert!(kind.to_string().contains("Data = 1 + 1u8"));
+//! # Ok(())
+//! # }
+//! ```
+mod discriminant;
+
+pub use discriminant::{
+    DiscriminantEnum, DiscriminantValue, DiscriminantVariant, FieldShape, OtherVariant, check_duplicate_discriminants, derive_discriminant_impl, discriminant_impl, generate_code_newtype, generate_codec_impls,
+    generate_fieldless_from_repr_variants, generate_fieldless_try_from_variants, generate_fieldless_variants, generate_original_to_fieldless_match_arms, generate_schemars_impl, generate_serde_impls, generate_warning, schema_hash,
+};
@@ -22,6 +22,15 @@
 //! - A fieldless version of the enum (regardless of whether or not the enum is already fieldless)
 //! - An `impl` for `Discriminantable` on the fieldless version of the enum
 //! - Other utilities on the fieldless version to ease use
+//! - Optionally, `serde::Serialize` and `serde::Deserialize` implementations that write the
+//!   discriminant of each variant instead of its index (requires the `serde` feature)
+//! - Optionally, `schemars::JsonSchema` implementations describing that encoding (requires the
+//!   `schemars` feature)
+//! - Optionally, `discrimin_ant::Encode` and `discrimin_ant::Decode` implementations that write
+//!   the discriminant followed by each field to a byte buffer, without `serde` or an allocator
+//!
+//! The same code can also be generated with `#[derive(Discriminant)]` for enums that already have
+//! a primitive `repr`, such as `#[repr(u8)]`.
 //!
 //! Specifically, given the enum
 //! ```no_run
@@ -44,7 +53,12 @@
 //!     Five { x: u32 } = 5,
 //!     Six { x: u32 },
 //! }
+//! // Recompiles the enum when `DISCRIMIN_ANT_EXPLICIT` changes, see the `explicit` option.
+//! const _: Option<&str> = option_env!("DISCRIMIN_ANT_EXPLICIT");
 //! impl ComplexUEnum {
+//!     #[doc = "A hash of the name, repr, variant names and discriminants of [ComplexUEnum], for checking that peers agree on them."]
+//!     pub const SCHEMA_HASH: u64 = 0xac5e_b081_0175_52e3;
+//!
 //!     #[doc = r" Returns the discriminant of [Self]."]
 //!     pub const fn discriminant(&self) -> u8 {
 //!         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
@@ -52,12 +66,14 @@
 //! }
 //! impl discrimin_ant::Discriminantable for ComplexUEnum {
 //!     type Discriminant = u8;
+//!     const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
 //!
 //!     fn discriminant(&self) -> Self::Discriminant {
 //!         self.discriminant()
 //!     }
 //! }
 //! #[doc = "Fieldless representations of [ComplexUEnum]. Used to extract discriminants without fully constructing the enum."]
+//! #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 //! #[repr(u8)]
 //! pub enum ComplexUEnum_ {
 //!     #[doc = "A fieldless version of [ComplexUEnum::One], used to extract the variant's discriminant without needing to fully construct it."]
@@ -70,20 +86,58 @@
 //!     Six = 5 + 1u8,
 //! }
 //! impl ComplexUEnum_ {
+//!     #[doc = r" Every variant of [Self], in declaration order."]
+//!     pub const VARIANTS: [Self; 4] = [Self::One, Self::Two, Self::Five, Self::Six];
+//!     #[doc = r" The number of variants of [Self]."]
+//!     pub const COUNT: usize = 4;
+//!     #[doc = r" The discriminant of every variant of [Self], in declaration order."]
+//!     pub const DISCRIMINANTS: [u8; 4] = [Self::One as u8, Self::Two as u8, Self::Five as u8, Self::Six as u8];
+//!
 //!     #[doc = r" Returns the discriminant of [Self]."]
 //!     pub const fn discriminant(&self) -> u8 {
 //!         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
 //!     }
+//!
+//!     #[doc = r" Returns an iterator over every variant of [Self], in declaration order."]
+//!     pub fn iter() -> core::array::IntoIter<Self, 4> {
+//!         Self::VARIANTS.into_iter()
+//!     }
+//!
+//!     #[doc = r" Returns the name of the variant."]
+//!     pub const fn name(&self) -> &'static str {
+//!         match *self {
+//!             Self::One => "One",
+//!             Self::Two => "Two",
+//!             Self::Five => "Five",
+//!             Self::Six => "Six",
+//!         }
+//!     }
 //! }
-//! impl discrimin_ant::Discriminantable for ComplexUEnum_ {
-//!     type Discriminant = u8;
+//! impl core::fmt::Display for ComplexUEnum_ {
+//!     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//!         f.write_str(self.name())
+//!     }
+//! }
+//! impl core::str::FromStr for ComplexUEnum_ {
+//!     type Err = discrimin_ant::ParseVariantError;
 //!
-//!     fn discriminant(&self) -> Self::Discriminant {
-//!         self.discriminant()
+//!     fn from_str(s: &str) -> Result<Self, Self::Err> {
+//!         match s {
+//!             "One" => Ok(Self::One),
+//!             "Two" => Ok(Self::Two),
+//!             "Five" => Ok(Self::Five),
+//!             "Six" => Ok(Self::Six),
+//!             _ => s
+//!                 .parse::<u8>()
+//!                 .ok()
+//!                 .and_then(|discriminant| Self::try_from(discriminant).ok())
+//!                 .ok_or_else(|| discrimin_ant::ParseVariantError::new(s, stringify!(ComplexUEnum_))),
+//!         }
 //!     }
 //! }
 //! impl TryFrom<u8> for ComplexUEnum_ {
-//!     type Error = ();
+//!     type Error = discrimin_ant::UnknownDiscriminant<Self, u8>;
+//!
 //!     fn try_from(value: u8) -> Result<Self, Self::Error> {
 //!         if value == (1) {
 //!             return Ok(Self::One);
@@ -97,7 +151,7 @@
 //!         if value == (5 + 1u8) {
 //!             return Ok(Self::Six);
 //!         }
-//!         Err(())
+//!         Err(discrimin_ant::UnknownDiscriminant::new(value, stringify!(ComplexUEnum_)))
 //!     }
 //! }
 //! impl From<&ComplexUEnum> for ComplexUEnum_ {
@@ -110,6 +164,18 @@
 //!         }
 //!     }
 //! }
+//! impl ComplexUEnum_ {
+//!     #[doc = "A hash of the name, repr, variant names and discriminants of [ComplexUEnum], for checking that peers agree on them."]
+//!     pub const SCHEMA_HASH: u64 = 0xac5e_b081_0175_52e3;
+//! }
+//! impl discrimin_ant::Discriminantable for ComplexUEnum_ {
+//!     type Discriminant = u8;
+//!     const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+//!
+//!     fn discriminant(&self) -> Self::Discriminant {
+//!         self.discriminant()
+//!     }
+//! }
 //! ```
 //!
 //! Note that the `discriminant` attribute only supports [primitive representations](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations)
@@ -117,14 +183,116 @@
 //! Nevertheless, manual implementations of `Discriminant` can be made for any enum (and technically and other object).
 use proc_macro::TokenStream;
 
-mod discriminant;
-
 /// Assign a `repr` and produce a `discriminant` method. Also produce a fieldless version of the
 /// enum to allow for easier access to the discriminants without needing to construct one.
 ///
+/// Generic enums are supported, including lifetimes, const generics and where-clauses. The
+/// fieldless version of the enum is never generic since it has no fields.
+///
+/// The fieldless version lists its variants in `VARIANTS`, their discriminants in `DISCRIMINANTS`
+/// and their number in `COUNT`, all usable in `const` contexts. `iter()` walks the variants in
+/// declaration order.
+///
+/// Duplicate discriminants and implicit discriminants that overflow the repr are reported against
+/// the offending variant. Discriminants that reference constants are checked with `const`
+/// assertions that name both variants.
+///
 /// * `repr`: The representation for the enum. Should be one of the standard `u*` or `i*` data types.
 ///   See <https://doc.rust-lang.org/nomicon/other-reprs.html#repru-repri>
 ///   and <https://doc.rust-lang.org/reference/type-layout.html#primitive-representations>
+///   May be omitted if the enum already has a `repr`, such as `#[repr(u8)]`. Other representations
+///   are kept, so `#[repr(C)]` combined with `#[discriminant(u16)]` becomes `#[repr(C, u16)]`.
+/// * `serde`: Implement `serde::Serialize` and `serde::Deserialize` for the enum. Each variant is
+///   written as a tuple of its discriminant followed by its fields, so reordering variants does
+///   not change the serialized output. The fieldless enum is written as its discriminant.
+///   Requires the `serde` feature of `discrimin-ant`.
+///   * `serde(human_readable)`: Write the fieldless enum as its variant name in human-readable
+///     formats such as JSON or TOML. Both names and discriminants are accepted when reading.
+/// * `schemars`: Implement `schemars::JsonSchema` for the enum and the fieldless enum, describing
+///   the encoding of the `serde` option, which it requires. The enum becomes a `oneOf` with an
+///   array for each variant, starting with its discriminant as a `const`. The fieldless enum becomes
+///   an integer `enum` of its discriminants. Requires the `schemars` feature of `discrimin-ant`.
+/// * `codec`: Implement `discrimin_ant::Encode` and `discrimin_ant::Decode` for the enum and the
+///   fieldless enum, for targets that cannot afford `serde`. Each variant is written as its
+///   discriminant at the width of the repr followed by each of its fields in order, which must
+///   implement the same traits. The variant is chosen with the fieldless enum's `TryFrom` when
+///   decoding, and errors carry the byte offset of the value that could not be read. The repr
+///   cannot be `usize` or `isize`, whose width depends on the target.
+/// * `fieldless(..)`: Configure the fieldless version of the enum.
+///   * `name = Ident`: The name of the fieldless enum. Defaults to the name of the enum followed by `_`.
+///   * `vis = pub(crate)`: The visibility of the fieldless enum. Defaults to the visibility of the enum.
+///   * `derive(..)`: The traits to derive for the fieldless enum. Defaults to
+///     `derive(Clone, Copy, Debug, PartialEq, Eq, Hash)`.
+///   * `skip`: Do not generate the fieldless enum at all.
+///   * `error = Type`: The error returned by `TryFrom` when a value is not a discriminant. The
+///     error is built with `From` for the repr. Defaults to `discrimin_ant::UnknownDiscriminant`,
+///     which carries the rejected value and the name of the enum.
+///   * `retired_error`: Return `discrimin_ant::DiscriminantError` from `TryFrom`, which tells
+///     retired and reserved discriminants apart from unknown ones.
+/// * `explicit`: Require every variant to have an explicit discriminant, so inserting a variant
+///   cannot silently renumber the ones after it. Variants without one are reported along with the
+///   value they would currently get.
+///   To turn this on for a whole workspace, set `DISCRIMIN_ANT_EXPLICIT = "1"` in the `[env]` table
+///   of `.cargo/config.toml`. It applies to every local package, i.e. the members of the workspace
+///   and path dependencies, but not to packages from a registry or git repository. It can be
+///   turned off for a single enum with `implicit`. Unless one of the two is given, the enum reads
+///   the variable with `option_env!` so that it is recompiled when the variable changes.
+/// * `reserved(3, 7, 10..=19)`: Discriminants that no variant may use, such as those of deleted
+///   variants. Any variant that lands on one, explicitly or implicitly, fails to compile.
+/// * `lock = "discriminants.lock"`: Check the discriminants against a lock file, relative to the
+///   crate's `Cargo.toml`. The build fails if a locked discriminant changed, if a locked variant
+///   was removed instead of retired, or if a variant is not locked yet. Each enum has a section:
+///   ```text
+///   [Message]
+///   Ping = 1
+///   Data = 2
+///   ```
+///   Errors for missing sections and variants show the lines to add. Sections are named after
+///   the enum alone, so enums with the same name in different modules must not share a lock file
+///   unless each is given its own section with `lock(path = "discriminants.lock", section = "net::Message")`.
+/// * `schema_hash(fields)`: Also hash the names and types of the fields of each variant into
+///   `SCHEMA_HASH`. By default, `SCHEMA_HASH` covers the name of the enum, its repr, and the name
+///   and discriminant of each variant. The hash is the same on every platform and compiler, so
+///   peers can exchange it to check that they agree on the enum.
+/// * `c_header`: Write a header for C and C++ to `$OUT_DIR/discrimin-ant/{Enum}.h`, with an
+///   `enum class` using the repr as its underlying type for C++ and a `typedef enum` for C. Each
+///   value is checked with a `static_assert`, and each variant has a comment listing its Rust
+///   fields. Cargo only sets `OUT_DIR` for packages with a build script, so one is required, even
+///   if it is empty. Every discriminant must be an integer literal or arithmetic on them.
+/// * `proto`: Write a proto3 `enum` to `$OUT_DIR/discrimin-ant/{Enum}.proto` with the same
+///   numbering. Values are named in `SCREAMING_SNAKE_CASE` with the enum name as a prefix, so
+///   `ChatEvent::Joined` becomes `CHAT_EVENT_JOINED`. Fails if a discriminant does not fit in an
+///   `int32` or no variant has the discriminant `0`. Requires a build script, like `c_header`.
+///   * `proto(package = "chat.v1")`: The protobuf package of the file.
+/// * `typescript`: Write a TypeScript `const enum` to `$OUT_DIR/discrimin-ant/{Enum}.ts`. Fails if a
+///   discriminant is too large for a `number` to hold exactly. Requires a build script.
+/// * `python`: Write a Python `enum.IntEnum` to `$OUT_DIR/discrimin-ant/{enum}.py`, with the module
+///   named in `snake_case`. Fails if a variant is named after a Python keyword, such as `None`.
+///   Requires a build script.
+/// * `export_name = "net_message"`: The name of the files written by `c_header`, `proto`,
+///   `typescript` and `python`, without their extension. Defaults to the name of the enum, in
+///   `snake_case` for Python, and is used as given otherwise. Enums with the same name in
+///   different source files need their own names, since exporting a file that an enum in another
+///   source file wrote fails. Enums with the same name in one source file are not told apart.
+/// * `other = Ident`: Add a catch-all variant, such as `Unknown(u8)`, to the fieldless enum that
+///   holds any discriminant without a variant. Every value of the repr then converts with `From`,
+///   and `discriminant()` returns unknown values unchanged. The fieldless enum no longer has a
+///   `repr` since the catch-all variant carries its discriminant.
+/// * `code`: Generate an open newtype, such as `pub struct MessageCode(pub u8)`, that can hold
+///   any value of the repr. It has a `SCREAMING_SNAKE_CASE` constant for the discriminant of each
+///   variant, converts to and from the fieldless enum, and its `Debug` prints the variant name
+///   when the value is known.
+///   * `code(name = Ident)`: The name of the newtype. Defaults to the name of the enum followed by
+///     `Code`.
+/// * `rename_all = "snake_case"`: Convert the variant names given by `name()`, `Display` and
+///   `FromStr` of the fieldless enum to `snake_case`, `kebab-case` or `SCREAMING_SNAKE_CASE`.
+///   Also used by `serde(human_readable)`.
+///
+/// Each variant may also be given a `#[discriminant(rename = "name")]` attribute to override its
+/// name. `FromStr` accepts either the name of a variant or its discriminant.
+///
+/// A variant marked with `#[discriminant(retired)]` is removed from the enum, but its discriminant
+/// is kept so that it can never be used again. The remaining variants keep their discriminants.
 ///
 /// # Example
 /// Fieldless
@@ -188,10 +356,60 @@ mod discriminant;
 /// assert_eq!(FieldEnum_::Seven.discriminant(), 7);
 /// assert_eq!(FieldEnum_::Nine.discriminant(), 9);
 /// ```
+///
+/// With `serde`
+/// ```rust
+/// use discrimin_ant_proc::discriminant;
+/// #[discriminant(u8, serde)]
+/// enum Message {
+///     Ping = 1,
+///     Data(u16),
+///     Move { x: i32, y: i32 } = 5,
+/// }
+///
+/// assert_eq!(serde_json::to_string(&Message::Ping).unwrap(), "[1]");
+/// assert_eq!(serde_json::to_string(&Message::Data(7)).unwrap(), "[2,7]");
+/// assert_eq!(serde_json::to_string(&Message::Move { x: 1, y: 2 }).unwrap(), "[5,1,2]");
+/// ```
 #[proc_macro_attribute]
 pub fn discriminant(attr: TokenStream, item: TokenStream) -> TokenStream {
     let item = proc_macro2::TokenStream::from(item);
     let attr = proc_macro2::TokenStream::from(attr);
 
-    discriminant::discriminant_impl(attr, item).into()
+    discrimin_ant_codegen::discriminant_impl(attr, item).into()
+}
+
+/// Produce a `discriminant` method and a fieldless version of the enum, the same as the
+/// [`macro@discriminant`] attribute.
+///
+/// Since a derive cannot modify the enum, the enum must already have a primitive `repr`.
+/// The derive is configured with `#[discriminant(..)]` helper attributes, which accept the same
+/// options as the [`macro@discriminant`] attribute. The repr may also be given as `repr = u8`, in
+/// which case it must match the `repr` of the enum.
+///
+/// Note that the helper attribute shares its name with the [`macro@discriminant`] attribute, so the
+/// attribute cannot be imported in the same scope as the derive.
+///
+/// # Example
+/// ```rust
+/// use discrimin_ant_proc::Discriminant;
+/// #[derive(Discriminant)]
+/// #[discriminant(repr = u8, fieldless(name = MessageKind))]
+/// #[repr(u8)]
+/// enum Message {
+///     Ping = 1,
+///     Data(u16),
+///     Move { x: i32, y: i32 } = 5,
+/// }
+///
+/// assert_eq!(Message::Ping.discriminant(), 1);
+/// assert_eq!(Message::Data(0).discriminant(), 2);
+/// assert_eq!(MessageKind::from(&Message::Move { x: 0, y: 0 }), MessageKind::Move);
+/// assert_eq!(MessageKind::try_from(2), Ok(MessageKind::Data));
+/// ```
+#[proc_macro_derive(Discriminant, attributes(discriminant))]
+pub fn derive_discriminant(item: TokenStream) -> TokenStream {
+    let item = proc_macro2::TokenStream::from(item);
+
+    discrimin_ant_codegen::derive_discriminant_impl(item).into()
 }
@@ -0,0 +1,101 @@
+#![expect(missing_docs, clippy::unwrap_used)]
+use discrimin_ant::{Decode, DecodeError, DecodeErrorKind, DecodedDiscriminant, Encode, EncodeError, Endian};
+use discrimin_ant_proc::discriminant;
+
+#[discriminant(u8, codec)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    Ping = 1,
+    Data(u16, bool),
+    Move { x: i32, y: i32 } = 5,
+    Status(Status),
+    #[discriminant(retired)]
+    Old,
+}
+
+#[discriminant(u16, codec)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0x0102,
+    Failed([u8; 2]),
+}
+
+#[discriminant(i8, codec)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Level {
+    Low = -1,
+    High = 1,
+}
+
+#[discriminant(u8, codec)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Wrapper<T> {
+    Value(T) = 3,
+}
+
+/// Encodes a value into a fresh buffer, returning the bytes written.
+fn encode<T: Encode>(value: &T, endian: Endian) -> Vec<u8> {
+    let mut buffer = [0; 32];
+    let len = value.encode(&mut buffer, endian).unwrap();
+    buffer[..len].to_vec()
+}
+
+/// Creates the kind of error for a discriminant that is not that of a current variant.
+fn unknown(enum_name: &'static str, value: impl Into<DecodedDiscriminant>) -> DecodeErrorKind {
+    DecodeErrorKind::UnknownDiscriminant { enum_name, value: value.into() }
+}
+
+#[test]
+fn test_encode_writes_discriminant_and_fields() {
+    assert_eq!(encode(&Message::Ping, Endian::Little), [1]);
+    assert_eq!(encode(&Message::Data(0x0302, true), Endian::Little), [2, 2, 3, 1]);
+    assert_eq!(encode(&Message::Data(0x0302, true), Endian::Big), [2, 3, 2, 1]);
+    assert_eq!(encode(&Message::Move { x: 1, y: -1 }, Endian::Little), [5, 1, 0, 0, 0, 255, 255, 255, 255]);
+    assert_eq!(encode(&Message::Status(Status::Ok), Endian::Big), [6, 1, 2]);
+    assert_eq!(encode(&Message::Status(Status::Failed([7, 8])), Endian::Little), [6, 3, 1, 7, 8]);
+    assert_eq!(encode(&Wrapper::Value(-2_i16), Endian::Big), [3, 255, 254]);
+}
+
+#[test]
+fn test_round_trip() {
+    let messages = [Message::Ping, Message::Data(7, true), Message::Move { x: -4, y: 9 }, Message::Status(Status::Failed([1, 2]))];
+    for endian in [Endian::Little, Endian::Big] {
+        for message in &messages {
+            let bytes = encode(message, endian);
+            assert_eq!(Message::decode(&bytes, endian).unwrap(), (message.clone(), bytes.len()));
+        }
+    }
+}
+
+#[test]
+fn test_fieldless_codec() {
+    assert_eq!(encode(&Message_::Move, Endian::Little), [5]);
+    assert_eq!(encode(&Status_::Ok, Endian::Little), [2, 1]);
+    assert_eq!(Status_::decode(&[2, 1], Endian::Little).unwrap(), (Status_::Ok, 2));
+    assert_eq!(Message_::decode(&[4], Endian::Little).unwrap_err(), DecodeError::new(0, unknown("Message_", 4u8)));
+
+    assert_eq!(Level_::decode(&[0xff], Endian::Little).unwrap(), (Level_::Low, 1));
+    let error = Level_::decode(&[0xfe], Endian::Little).unwrap_err();
+    assert_eq!(error, DecodeError::new(0, unknown("Level_", -2i8)));
+    assert_eq!(error.to_string(), "unknown discriminant `-2` for `Level_` at byte 0");
+}
+
+#[test]
+fn test_decode_reports_offset() {
+    // The retired discriminant of `Old` is rejected along with unknown ones.
+    assert_eq!(Message::decode(&[7], Endian::Little).unwrap_err(), DecodeError::new(0, unknown("Message", 7u8)));
+    assert_eq!(Message::decode(&[5, 1, 0, 0, 0, 255], Endian::Little).unwrap_err(), DecodeError::new(5, DecodeErrorKind::UnexpectedEnd));
+    assert_eq!(Message::decode(&[2, 2, 3, 9], Endian::Little).unwrap_err(), DecodeError::new(3, DecodeErrorKind::InvalidBool));
+
+    let error = Message::decode(&[6, 9, 9], Endian::Little).unwrap_err();
+    assert_eq!(error, DecodeError::new(1, unknown("Status", 0x0909u16)));
+    assert_eq!(error.to_string(), "unknown discriminant `2313` for `Status` at byte 1");
+}
+
+#[test]
+fn test_encode_reports_offset() {
+    let mut buffer = [0; 4];
+    assert_eq!(Message::Move { x: 1, y: 2 }.encode(&mut buffer, Endian::Little), Err(EncodeError::new(1)));
+    assert_eq!(Message::Status(Status::Failed([1, 2])).encode(&mut buffer, Endian::Little), Err(EncodeError::new(4)));
+    assert_eq!(Message::Ping.encode(&mut [], Endian::Little), Err(EncodeError::new(0)));
+}
@@ -0,0 +1,38 @@
+#![expect(missing_docs)]
+use discrimin_ant::UnknownDiscriminant;
+use discrimin_ant_proc::Discriminant;
+
+#[derive(Discriminant)]
+#[repr(i16)]
+pub enum FieldEnum {
+    NOne(u8) = -1,
+    One { item: u8 } = 1,
+    Two,
+}
+
+#[derive(Discriminant)]
+#[discriminant(repr = u8, serde)]
+#[discriminant(fieldless(name = MessageKind))]
+#[repr(C, u8)]
+pub enum Message {
+    Ping = 1,
+    Data(u16),
+}
+
+#[test]
+fn test_derived_discriminants() {
+    assert_eq!(FieldEnum::NOne(0).discriminant(), -1);
+    assert_eq!(FieldEnum::One { item: 0 }.discriminant(), 1);
+    assert_eq!(FieldEnum::Two.discriminant(), 2);
+
+    assert_eq!(FieldEnum_::from(&FieldEnum::One { item: 0 }), FieldEnum_::One);
+    assert_eq!(FieldEnum_::try_from(-1), Ok(FieldEnum_::NOne));
+    assert_eq!(FieldEnum_::try_from(3), Err(UnknownDiscriminant::new(3, "FieldEnum_")));
+}
+
+#[test]
+fn test_derived_options() {
+    assert_eq!(MessageKind::from(&Message::Ping), MessageKind::Ping);
+    assert_eq!(Message::Data(4).discriminant(), 2);
+    assert_eq!(serde_json::to_string(&Message::Data(4)).ok().as_deref(), Some("[2,4]"));
+}
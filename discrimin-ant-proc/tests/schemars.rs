@@ -0,0 +1,83 @@
+#![expect(missing_docs, clippy::unwrap_used)]
+use discrimin_ant::schemars::{JsonSchema, schema_for};
+use discrimin_ant_proc::discriminant;
+use serde_json::{Value, json};
+
+#[discriminant(u8, serde, schemars)]
+pub enum Message {
+    Ping = 1,
+    Data(u16, bool),
+    Move { x: i32, y: i32 } = 5,
+}
+
+/// Returns the schema of `T` as JSON, without the `$schema` and `title` keywords.
+fn schema_of<T: JsonSchema>() -> Value {
+    let mut schema = serde_json::to_value(schema_for!(T)).unwrap();
+    let object = schema.as_object_mut().unwrap();
+    object.remove("$schema");
+    object.remove("title");
+    schema
+}
+
+#[test]
+fn test_schema_uses_discriminants() {
+    let expected = json!({
+        "description": "A variant of Message, written as its discriminant followed by its fields.",
+        "oneOf": [
+            { "title": "Ping", "type": "array", "prefixItems": [{ "const": 1 }], "items": false, "minItems": 1, "maxItems": 1 },
+            {
+                "title": "Data",
+                "type": "array",
+                "prefixItems": [{ "const": 2 }, { "type": "integer", "format": "uint16", "minimum": 0, "maximum": 65535 }, { "type": "boolean" }],
+                "items": false,
+                "minItems": 3,
+                "maxItems": 3
+            },
+            {
+                "title": "Move",
+                "type": "array",
+                "prefixItems": [{ "const": 5 }, { "type": "integer", "format": "int32" }, { "type": "integer", "format": "int32" }],
+                "items": false,
+                "minItems": 3,
+                "maxItems": 3
+            }
+        ]
+    });
+    assert_eq!(schema_of::<Message>(), expected);
+    assert_eq!(serde_json::to_value(Message::Data(7, false)).unwrap(), json!([2, 7, false]));
+}
+
+#[test]
+fn test_fieldless_schema() {
+    assert_eq!(schema_of::<Message_>(), json!({ "type": "integer", "enum": [1, 2, 5] }));
+}
+
+#[discriminant(i16, serde(human_readable), schemars, rename_all = "snake_case", other = Unknown)]
+pub enum Command {
+    Stop = -1,
+    Start = 1,
+    Restart { delay: u32 },
+}
+
+#[test]
+fn test_human_readable_schema() {
+    let expected = json!({
+        "oneOf": [
+            { "type": "string", "enum": ["stop", "start", "restart"] },
+            { "type": "integer", "format": "int16", "minimum": -32768, "maximum": 32767 }
+        ]
+    });
+    assert_eq!(schema_of::<Command_>(), expected);
+}
+
+#[discriminant(u8, serde, schemars)]
+pub enum Wrapper<T> {
+    Empty,
+    Value(T),
+}
+
+#[test]
+fn test_generic_schema() {
+    let schema = schema_of::<Wrapper<String>>();
+    assert_eq!(schema["oneOf"][1]["prefixItems"], json!([{ "const": 1 }, { "type": "string" }]));
+}
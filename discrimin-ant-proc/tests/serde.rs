@@ -0,0 +1,132 @@
+#![expect(missing_docs, clippy::unwrap_used)]
+use discrimin_ant_proc::discriminant;
+
+#[discriminant(u8, serde)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Message {
+    Ping = 1,
+    Data(u16, bool),
+    Move { x: i32, y: i32 } = 5,
+}
+
+/// The same as [Message], but with the variants in a different order.
+#[discriminant(u8, serde)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReorderedMessage {
+    Move { x: i32, y: i32 } = 5,
+    Ping = 1,
+    Data(u16, bool),
+}
+
+#[test]
+fn test_serialize_writes_discriminant() {
+    assert_eq!(bincode::serialize(&Message::Ping).unwrap(), [1]);
+    assert_eq!(bincode::serialize(&Message::Data(0x0302, true)).unwrap(), [2, 2, 3, 1]);
+    assert_eq!(bincode::serialize(&Message::Move { x: 1, y: -1 }).unwrap(), [5, 1, 0, 0, 0, 255, 255, 255, 255]);
+
+    assert_eq!(serde_json::to_string(&Message::Ping).unwrap(), "[1]");
+    assert_eq!(serde_json::to_string(&Message::Data(7, false)).unwrap(), "[2,7,false]");
+    assert_eq!(serde_json::to_string(&Message::Move { x: 1, y: 2 }).unwrap(), "[5,1,2]");
+}
+
+#[test]
+fn test_round_trip() {
+    for message in [Message::Ping, Message::Data(7, true), Message::Move { x: -4, y: 9 }] {
+        let bytes = bincode::serialize(&message).unwrap();
+        assert_eq!(bincode::deserialize::<Message>(&bytes).unwrap(), message);
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), message);
+    }
+}
+
+#[test]
+fn test_reordering_does_not_change_bytes() {
+    assert_eq!(bincode::serialize(&Message::Ping).unwrap(), bincode::serialize(&ReorderedMessage::Ping).unwrap());
+    assert_eq!(bincode::serialize(&Message::Data(3, true)).unwrap(), bincode::serialize(&ReorderedMessage::Data(3, true)).unwrap());
+    assert_eq!(
+        bincode::serialize(&Message::Move { x: 3, y: 4 }).unwrap(),
+        bincode::serialize(&ReorderedMessage::Move { x: 3, y: 4 }).unwrap()
+    );
+}
+
+#[test]
+fn test_unknown_discriminant() {
+    let error = serde_json::from_str::<Message>("[3]").unwrap_err();
+    assert!(error.to_string().contains("unknown discriminant `3` for `Message`"), "{error}");
+
+    assert!(serde_json::from_str::<Message>("[2,7]").is_err());
+}
+
+#[discriminant(i16, serde(human_readable))]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Stop = -1,
+    Start = 1,
+    Restart { delay: u32 },
+}
+
+#[test]
+fn test_fieldless_writes_discriminant() {
+    assert_eq!(bincode::serialize(&Message_::Move).unwrap(), [5]);
+    assert_eq!(serde_json::to_string(&Message_::Move).unwrap(), "5");
+
+    assert_eq!(bincode::deserialize::<Message_>(&[2]).unwrap(), Message_::Data);
+    assert_eq!(serde_json::from_str::<Message_>("1").unwrap(), Message_::Ping);
+    assert!(serde_json::from_str::<Message_>("\"Ping\"").is_err());
+
+    let error = bincode::deserialize::<Message_>(&[4]).unwrap_err();
+    assert!(error.to_string().contains("unknown discriminant `4` for `Message_`"), "{error}");
+}
+
+#[test]
+fn test_fieldless_human_readable() {
+    assert_eq!(serde_json::to_string(&Command_::Stop).unwrap(), "\"Stop\"");
+    assert_eq!(serde_json::to_string(&Command_::Restart).unwrap(), "\"Restart\"");
+    assert_eq!(bincode::serialize(&Command_::Stop).unwrap(), [255, 255]);
+    assert_eq!(bincode::serialize(&Command_::Restart).unwrap(), [2, 0]);
+
+    assert_eq!(serde_json::from_str::<Command_>("\"Start\"").unwrap(), Command_::Start);
+    assert_eq!(serde_json::from_str::<Command_>("-1").unwrap(), Command_::Stop);
+    assert_eq!(bincode::deserialize::<Command_>(&[1, 0]).unwrap(), Command_::Start);
+    assert!(serde_json::from_str::<Command_>("\"Pause\"").is_err());
+    assert!(serde_json::from_str::<Command_>("70000").is_err());
+}
+
+#[discriminant(u8, serde)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Frame<'a, T> {
+    Text(&'a str) = 1,
+    Value { value: T },
+}
+
+#[test]
+fn test_generic_round_trip() {
+    let json = serde_json::to_string(&Frame::<u16>::Text("hello")).unwrap();
+    assert_eq!(json, r#"[1,"hello"]"#);
+    assert_eq!(serde_json::from_str::<Frame<u16>>(&json).unwrap(), Frame::Text("hello"));
+
+    let frame: Frame<u16> = Frame::Value { value: 0x0201 };
+    let bytes = bincode::serialize(&frame).unwrap();
+    assert_eq!(bytes, [2, 1, 2]);
+    assert_eq!(bincode::deserialize::<Frame<u16>>(&bytes).unwrap(), Frame::Value { value: 0x0201 });
+}
+
+#[discriminant(u8, serde(human_readable), other = Unknown)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event {
+    Started = 1,
+    Stopped(u32),
+}
+
+#[test]
+fn test_serde_other_variant() {
+    assert_eq!(serde_json::to_string(&Event_::Unknown(9)).unwrap(), "9");
+    assert_eq!(serde_json::to_string(&Event_::Started).unwrap(), "\"Started\"");
+    assert_eq!(serde_json::from_str::<Event_>("9").unwrap(), Event_::Unknown(9));
+    assert_eq!(bincode::deserialize::<Event_>(&bincode::serialize(&Event_::Unknown(9)).unwrap()).unwrap(), Event_::Unknown(9));
+
+    // The original enum still cannot represent unknown discriminants
+    assert_eq!(serde_json::from_str::<Event>("[2,5]").unwrap(), Event::Stopped(5));
+    assert!(serde_json::from_str::<Event>("[9]").is_err());
+}
@@ -1,4 +1,4 @@
-#![expect(missing_docs)]
+#![expect(missing_docs, clippy::unwrap_used)]
 use discrimin_ant_proc::discriminant;
 
 #[discriminant(i8)]
@@ -60,3 +60,346 @@ fn test_fieldless_discriminants() {
     assert_eq!(FieldlessEnum_::Seven.discriminant(), 7);
     assert_eq!(FieldlessEnum_::Nine.discriminant(), 9);
 }
+
+#[discriminant(u8)]
+pub enum GenericEnum<'a, T, const N: usize>
+where
+    T: Copy,
+{
+    Borrowed(&'a T) = 1,
+    Owned([T; N]),
+    Empty = 5,
+}
+
+#[test]
+fn test_generic_discriminants() {
+    let value = 7u32;
+    assert_eq!(GenericEnum::<u32, 2>::Borrowed(&value).discriminant(), 1);
+    assert_eq!(GenericEnum::<u32, 2>::Owned([1, 2]).discriminant(), 2);
+    assert_eq!(GenericEnum::<u32, 2>::Empty.discriminant(), 5);
+
+    assert_eq!(discrimin_ant::Discriminantable::discriminant(&GenericEnum::<u32, 2>::Owned([1, 2])), 2);
+    assert_eq!(GenericEnum_::from(&GenericEnum::<u32, 2>::Borrowed(&value)).discriminant(), 1);
+    assert_eq!(GenericEnum_::from(&GenericEnum::<u32, 2>::Empty).discriminant(), 5);
+}
+
+mod custom_fieldless {
+    use discrimin_ant_proc::discriminant;
+
+    #[discriminant(u8, fieldless(name = MessageKind, vis = pub(crate), derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)))]
+    pub enum Message {
+        Ping = 1,
+        Data(#[expect(dead_code)] u16),
+    }
+
+    #[discriminant(u8, fieldless(skip))]
+    pub enum Skipped {
+        A = 3,
+        B,
+    }
+
+    /// Would collide with the fieldless enum if it were generated.
+    #[expect(dead_code)]
+    pub struct Skipped_;
+
+    #[test]
+    fn test_custom_fieldless() {
+        assert_eq!(MessageKind::from(&Message::Ping), MessageKind::Ping);
+        assert_eq!(MessageKind::from(&Message::Data(3)), MessageKind::Data);
+        assert!(MessageKind::Ping < MessageKind::Data);
+        assert_eq!(MessageKind::try_from(1), Ok(MessageKind::Ping));
+    }
+
+    #[test]
+    fn test_skipped_fieldless() {
+        assert_eq!(Skipped::A.discriminant(), 3);
+        assert_eq!(Skipped::B.discriminant(), 4);
+    }
+}
+
+const FIRST_CONSTANT: u16 = 10;
+const SECOND_CONSTANT: u16 = 20;
+
+#[discriminant(u16)]
+pub enum ConstantEnum {
+    First = FIRST_CONSTANT,
+    Second = SECOND_CONSTANT,
+    AfterSecond,
+    Literal = 11,
+}
+
+#[test]
+fn test_constant_discriminants() {
+    assert_eq!(ConstantEnum::First.discriminant(), 10);
+    assert_eq!(ConstantEnum::Second.discriminant(), 20);
+    assert_eq!(ConstantEnum::AfterSecond.discriminant(), 21);
+    assert_eq!(ConstantEnum::Literal.discriminant(), 11);
+}
+
+#[repr(C, u16)]
+#[discriminant]
+pub enum ExistingReprEnum {
+    First(u8) = 300,
+    Second { value: u32 },
+}
+
+#[test]
+fn test_existing_repr_discriminants() {
+    assert_eq!(ExistingReprEnum::First(0).discriminant(), 300);
+    assert_eq!(ExistingReprEnum::Second { value: 0 }.discriminant(), 301);
+    assert_eq!(ExistingReprEnum_::try_from(301), Ok(ExistingReprEnum_::Second));
+}
+
+const FIELD_ENUM_DISCRIMINANT_SUM: i32 = {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < FieldEnum_::COUNT {
+        sum += FieldEnum_::DISCRIMINANTS[i] as i32;
+        i += 1;
+    }
+    sum
+};
+
+#[test]
+fn test_variant_enumeration() {
+    assert_eq!(FieldEnum_::COUNT, 7);
+    assert_eq!(FieldEnum_::VARIANTS.len(), FieldEnum_::COUNT);
+    assert_eq!(FieldEnum_::VARIANTS[3], FieldEnum_::Three);
+    assert_eq!(FieldEnum_::DISCRIMINANTS, [-1, 1, 2, 3, 5, 7, 9]);
+    assert_eq!(FIELD_ENUM_DISCRIMINANT_SUM, 26);
+
+    let discriminants = FieldEnum_::iter().map(|variant| variant.discriminant()).collect::<Vec<_>>();
+    assert_eq!(discriminants, FieldEnum_::DISCRIMINANTS);
+    assert!(FieldEnum_::iter().all(|variant| FieldEnum_::try_from(variant.discriminant()) == Ok(variant)));
+}
+
+#[discriminant(u16, rename_all = "kebab-case")]
+pub enum Command {
+    Ping = 1,
+    MoveTo { x: i32, y: i32 },
+    #[discriminant(rename = "halt")]
+    ShutDown = 10,
+}
+
+pub mod keyword {
+    // Also covers the variants of the fieldless enum, which keep the raw identifiers.
+    #![expect(non_camel_case_types)]
+    use discrimin_ant_proc::discriminant;
+
+    #[discriminant(u8, rename_all = "kebab-case")]
+    pub enum Keyword {
+        r#async,
+        r#MoveTo,
+        Plain,
+    }
+}
+use keyword::{Keyword, Keyword_};
+
+#[test]
+fn test_variant_names() {
+    assert_eq!(Command_::Ping.name(), "ping");
+    assert_eq!(Command_::MoveTo.name(), "move-to");
+    assert_eq!(Command_::ShutDown.name(), "halt");
+    assert_eq!(Command_::from(&Command::MoveTo { x: 0, y: 0 }).to_string(), "move-to");
+    assert_eq!(FieldlessEnum_::NOne.to_string(), "NOne");
+    assert_eq!(Keyword_::from(&Keyword::r#async).name(), "async");
+    assert_eq!(Keyword_::MoveTo.to_string(), "move-to");
+}
+
+#[test]
+fn test_variant_from_str() {
+    assert_eq!("ping".parse(), Ok(Command_::Ping));
+    assert_eq!("move-to".parse(), Ok(Command_::MoveTo));
+    assert_eq!("halt".parse(), Ok(Command_::ShutDown));
+    assert_eq!("10".parse(), Ok(Command_::ShutDown));
+    assert_eq!("-1".parse(), Ok(FieldlessEnum_::NOne));
+    assert_eq!("async".parse(), Ok(Keyword_::r#async));
+    assert!("r#async".parse::<Keyword_>().is_err());
+
+    let error = "ShutDown".parse::<Command_>().unwrap_err();
+    assert_eq!(error.input(), "ShutDown");
+    assert_eq!(error.enum_name(), "Command_");
+    assert_eq!(error.to_string(), "unknown variant `ShutDown` for `Command_`");
+
+    let error = "3".parse::<Command_>().unwrap_err();
+    assert_eq!(error.to_string(), "unknown variant `3` for `Command_`");
+
+    let long = "é".repeat(40);
+    let error = long.parse::<Command_>().unwrap_err();
+    assert!(error.is_truncated());
+    assert_eq!(error.input(), "é".repeat(32));
+}
+
+#[test]
+fn test_unknown_discriminant() {
+    let error = FieldlessEnum_::try_from(4).unwrap_err();
+    assert_eq!(*error.value(), 4);
+    assert_eq!(error.enum_name(), "FieldlessEnum_");
+    assert_eq!(error.to_string(), "unknown discriminant `4` for `FieldlessEnum_`");
+
+    let error: Box<dyn core::error::Error> = Box::new(error);
+    assert_eq!(error.to_string(), "unknown discriminant `4` for `FieldlessEnum_`");
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BadOpcode(pub u8);
+
+impl From<u8> for BadOpcode {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+#[discriminant(u8, fieldless(error = BadOpcode))]
+pub enum Opcode {
+    Nop,
+    Load(u32),
+    Store(u32),
+}
+
+#[test]
+fn test_custom_unknown_discriminant() {
+    assert_eq!(Opcode_::try_from(1), Ok(Opcode_::Load));
+    assert_eq!(Opcode_::from(&Opcode::Store(0)), Opcode_::Store);
+    assert_eq!(Opcode_::try_from(3), Err(BadOpcode(3)));
+}
+
+#[discriminant(u8, other = Unknown)]
+pub enum Packet {
+    Hello = 1,
+    Data(Vec<u8>),
+    Goodbye = 10,
+}
+
+#[test]
+fn test_other_variant() {
+    assert_eq!(Packet_::from(2), Packet_::Data);
+    assert_eq!(Packet_::from(7), Packet_::Unknown(7));
+    assert_eq!(Packet_::from(&Packet::Goodbye), Packet_::Goodbye);
+
+    for value in 0..=u8::MAX {
+        assert_eq!(Packet_::from(value).discriminant(), value);
+        assert_eq!(discrimin_ant::Discriminantable::discriminant(&Packet_::from(value)), value);
+    }
+
+    assert_eq!(Packet_::COUNT, 3);
+    assert_eq!(Packet_::DISCRIMINANTS, [1, 2, 10]);
+    assert_eq!(Packet_::Unknown(7).name(), "Unknown");
+    assert_eq!(Packet_::Unknown(7).to_string(), "7");
+    assert_eq!(Packet_::Data.to_string(), "Data");
+    assert_eq!("7".parse(), Ok(Packet_::Unknown(7)));
+    assert_eq!("10".parse(), Ok(Packet_::Goodbye));
+    assert!("Unknown".parse::<Packet_>().is_err());
+}
+
+#[discriminant(u8, code)]
+pub enum Instruction {
+    Halt,
+    Jump(u16) = 4,
+    JumpIfZero(u16),
+}
+
+#[discriminant(u8, other = Unknown, code(name = PacketKindCode))]
+pub enum PacketKind {
+    Hello = 1,
+    Goodbye = 10,
+}
+
+#[test]
+fn test_code_newtype() {
+    assert_eq!(InstructionCode::HALT, InstructionCode(0));
+    assert_eq!(InstructionCode::JUMP.discriminant(), 4);
+    assert_eq!(InstructionCode::JUMP_IF_ZERO.0, Instruction::JumpIfZero(0).discriminant());
+    assert_eq!(InstructionCode::from(&Instruction::Jump(7)), InstructionCode::JUMP);
+    assert_eq!(InstructionCode::from(Instruction_::JumpIfZero), InstructionCode::JUMP_IF_ZERO);
+    assert_eq!(u8::from(InstructionCode::JUMP), 4);
+    assert_eq!(discrimin_ant::Discriminantable::discriminant(&InstructionCode(9)), 9);
+
+    assert_eq!(Instruction_::try_from(InstructionCode::JUMP), Ok(Instruction_::Jump));
+    assert!(Instruction_::try_from(InstructionCode(9)).is_err());
+    assert_eq!(InstructionCode(9).name(), None);
+    assert_eq!(InstructionCode::HALT.name(), Some("Halt"));
+
+    assert_eq!(format!("{:?}", InstructionCode::JUMP_IF_ZERO), "JumpIfZero");
+    assert_eq!(format!("{:?}", InstructionCode(9)), "InstructionCode(9)");
+
+    assert_eq!(PacketKind_::from(PacketKindCode(7)), PacketKind_::Unknown(7));
+    assert_eq!(PacketKindCode::from(PacketKind_::Unknown(7)), PacketKindCode(7));
+    assert_eq!(PacketKind_::from(PacketKindCode::GOODBYE), PacketKind_::Goodbye);
+}
+
+#[discriminant(u16, explicit)]
+pub enum WireMessage {
+    Ping = 1,
+    Pong = 2,
+    Data(u32) = 10,
+}
+
+#[test]
+fn test_explicit_discriminants() {
+    assert_eq!(WireMessage_::DISCRIMINANTS, [1, 2, 10]);
+    assert_eq!(WireMessage::Data(0).discriminant(), 10);
+}
+
+const LEGACY_BASE: u8 = 40;
+
+#[discriminant(u8, reserved(3, 10..=19), fieldless(retired_error))]
+pub enum Protocol {
+    Hello = 1,
+    #[discriminant(retired)]
+    OldData(u16),
+    Data(u32) = 4,
+    Bye,
+    Legacy = LEGACY_BASE,
+}
+
+#[test]
+fn test_retired_discriminants() {
+    use discrimin_ant::{DiscriminantError, UnknownDiscriminant};
+
+    assert_eq!(Protocol::Data(0).discriminant(), 4);
+    assert_eq!(Protocol::Bye.discriminant(), 5);
+    assert_eq!(Protocol_::COUNT, 4);
+    assert_eq!(Protocol_::DISCRIMINANTS, [1, 4, 5, 40]);
+
+    assert_eq!(Protocol_::try_from(5), Ok(Protocol_::Bye));
+    assert_eq!(Protocol_::try_from(2), Err(DiscriminantError::Retired(UnknownDiscriminant::new(2, "Protocol_"))));
+    assert!(Protocol_::try_from(3).unwrap_err().is_retired());
+    assert!(Protocol_::try_from(15).unwrap_err().is_retired());
+    assert_eq!(Protocol_::try_from(20), Err(DiscriminantError::Unknown(UnknownDiscriminant::new(20, "Protocol_"))));
+    assert_eq!(Protocol_::try_from(2).unwrap_err().to_string(), "retired discriminant `2` for `Protocol_`");
+}
+
+const DELETED: u8 = 10;
+
+#[discriminant(u8, lock = "tests/discriminants.lock")]
+pub enum Status {
+    Active = 1,
+    Suspended,
+    #[discriminant(retired)]
+    Closed,
+    Deleted = DELETED,
+}
+
+#[test]
+fn test_locked_discriminants() {
+    assert_eq!(Status::Suspended.discriminant(), 2);
+    assert_eq!(Status::Deleted.discriminant(), 10);
+    assert_eq!(Status_::DISCRIMINANTS, [1, 2, 10]);
+}
+
+#[test]
+fn test_schema_hash() {
+    use discrimin_ant::{Discriminantable, combine_schema_hashes};
+
+    const fn fingerprint<A: Discriminantable, B: Discriminantable>() -> u64 {
+        combine_schema_hashes(&[A::SCHEMA_HASH, B::SCHEMA_HASH])
+    }
+
+    assert_eq!(<Packet as Discriminantable>::SCHEMA_HASH, Packet::SCHEMA_HASH);
+    assert_eq!(<Packet_ as Discriminantable>::SCHEMA_HASH, Packet::SCHEMA_HASH);
+    assert_eq!(<InstructionCode as Discriminantable>::SCHEMA_HASH, Instruction::SCHEMA_HASH);
+    assert_ne!(Packet::SCHEMA_HASH, Instruction::SCHEMA_HASH);
+    assert_eq!(fingerprint::<Packet, Instruction>(), combine_schema_hashes(&[Packet::SCHEMA_HASH, Instruction::SCHEMA_HASH]));
+    assert_ne!(fingerprint::<Packet, Instruction>(), fingerprint::<Instruction, Packet>());
+}
@@ -0,0 +1,266 @@
+//! A compact binary encoding that needs neither `serde` nor an allocator, for use with the
+//! `codec` option of the `discriminant` attribute.
+//!
+//! Values are written back to back without any framing: integers and floats at their full width
+//! in the chosen byte order, `bool` as a single `0` or `1` byte, `char` as its `u32` code point and
+//! arrays as each of their elements in order.
+use crate::error::{DecodeError, DecodeErrorKind, EncodeError};
+
+/// The byte order that integers and floats are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// The least significant byte first.
+    #[default]
+    Little,
+    /// The most significant byte first, also known as network byte order.
+    Big,
+}
+
+/// A value that can be written to a byte buffer.
+pub trait Encode {
+    /// Writes the value to the start of `buffer`, returning the number of bytes written.
+    ///
+    /// * `buffer`: The buffer to write to.
+    /// * `endian`: The byte order to write integers and floats in.
+    ///
+    /// # Errors
+    /// Fails if the value does not fit in `buffer`. The contents of `buffer` are unspecified
+    /// afterwards.
+    fn encode(&self, buffer: &mut [u8], endian: Endian) -> Result<usize, EncodeError>;
+}
+
+/// A value that can be read from a byte buffer.
+pub trait Decode: Sized {
+    /// Reads a value from the start of `buffer`, returning it along with the number of bytes read.
+    ///
+    /// * `buffer`: The buffer to read from.
+    /// * `endian`: The byte order that integers and floats were written in.
+    ///
+    /// # Errors
+    /// Fails if `buffer` ends early or does not hold a valid value. The error holds the offset in
+    /// `buffer` of the value that could not be read.
+    fn decode(buffer: &[u8], endian: Endian) -> Result<(Self, usize), DecodeError>;
+}
+
+/// Writes values one after another to a byte buffer, keeping track of the offset of each.
+///
+/// ```rust
+/// use discrimin_ant::{Encoder, Endian};
+///
+/// let mut buffer = [0; 8];
+/// let mut encoder = Encoder::new(&mut buffer, Endian::Big);
+/// encoder.encode(&0x0102_u16).unwrap();
+/// encoder.encode(&true).unwrap();
+/// assert_eq!(encoder.position(), 3);
+/// assert_eq!(buffer[..3], [1, 2, 1]);
+/// ```
+#[derive(Debug)]
+pub struct Encoder<'a> {
+    /// The buffer to write to.
+    buffer: &'a mut [u8],
+    /// The number of bytes written so far.
+    position: usize,
+    /// The byte order to write integers and floats in.
+    endian: Endian,
+}
+
+impl<'a> Encoder<'a> {
+    /// Creates an encoder that writes to the start of `buffer`.
+    ///
+    /// * `buffer`: The buffer to write to.
+    /// * `endian`: The byte order to write integers and floats in.
+    pub const fn new(buffer: &'a mut [u8], endian: Endian) -> Self {
+        Self { buffer, position: 0, endian }
+    }
+
+    /// Writes a value after those already written.
+    ///
+    /// * `value`: The value.
+    ///
+    /// # Errors
+    /// Fails if the value does not fit in the rest of the buffer. The offset of the error is from
+    /// the start of the buffer.
+    pub fn encode<T: Encode + ?Sized>(&mut self, value: &T) -> Result<(), EncodeError> {
+        let rest = self.buffer.get_mut(self.position..).unwrap_or_default();
+        let written = value.encode(rest, self.endian).map_err(|error| error.nested_at(self.position))?;
+        self.position = self.position.saturating_add(written);
+        Ok(())
+    }
+
+    /// Writes raw bytes after those already written.
+    ///
+    /// * `bytes`: The bytes.
+    ///
+    /// # Errors
+    /// Fails if the bytes do not fit in the rest of the buffer.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let end = self.position.saturating_add(bytes.len());
+        let target = self.buffer.get_mut(self.position..end).ok_or_else(|| EncodeError::new(self.position))?;
+        target.copy_from_slice(bytes);
+        self.position = end;
+        Ok(())
+    }
+
+    /// Returns the number of bytes written so far.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the byte order that integers and floats are written in.
+    pub const fn endian(&self) -> Endian {
+        self.endian
+    }
+}
+
+/// Reads values one after another from a byte buffer, keeping track of the offset of each.
+///
+/// ```rust
+/// use discrimin_ant::{Decoder, Endian};
+///
+/// let mut decoder = Decoder::new(&[1, 2, 1], Endian::Big);
+/// assert_eq!(decoder.decode::<u16>().unwrap(), 0x0102);
+/// assert!(decoder.decode::<bool>().unwrap());
+/// assert_eq!(decoder.decode::<u8>().unwrap_err().offset(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Decoder<'a> {
+    /// The buffer to read from.
+    buffer: &'a [u8],
+    /// The number of bytes read so far.
+    position: usize,
+    /// The byte order that integers and floats were written in.
+    endian: Endian,
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a decoder that reads from the start of `buffer`.
+    ///
+    /// * `buffer`: The buffer to read from.
+    /// * `endian`: The byte order that integers and floats were written in.
+    pub const fn new(buffer: &'a [u8], endian: Endian) -> Self {
+        Self { buffer, position: 0, endian }
+    }
+
+    /// Reads a value after those already read.
+    ///
+    /// # Errors
+    /// Fails if the rest of the buffer does not hold a valid value. The offset of the error is from
+    /// the start of the buffer.
+    pub fn decode<T: Decode>(&mut self) -> Result<T, DecodeError> {
+        let rest = self.buffer.get(self.position..).unwrap_or_default();
+        let (value, read) = T::decode(rest, self.endian).map_err(|error| error.nested_at(self.position))?;
+        self.position = self.position.saturating_add(read);
+        Ok(value)
+    }
+
+    /// Reads `N` raw bytes after those already read.
+    ///
+    /// # Errors
+    /// Fails if fewer than `N` bytes are left.
+    pub fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let end = self.position.saturating_add(N);
+        let bytes = self
+            .buffer
+            .get(self.position..end)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| DecodeError::new(self.position, DecodeErrorKind::UnexpectedEnd))?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    /// Returns the number of bytes read so far.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the byte order that integers and floats were written in.
+    pub const fn endian(&self) -> Endian {
+        self.endian
+    }
+}
+
+/// Implements [`Encode`] and [`Decode`] for numbers, writing them at their full width.
+macro_rules! impl_codec_for_numbers {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, buffer: &mut [u8], endian: Endian) -> Result<usize, EncodeError> {
+                    let bytes = match endian {
+                        Endian::Little => self.to_le_bytes(),
+                        Endian::Big => self.to_be_bytes(),
+                    };
+                    let mut encoder = Encoder::new(buffer, endian);
+                    encoder.write_bytes(&bytes)?;
+                    Ok(encoder.position())
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(buffer: &[u8], endian: Endian) -> Result<(Self, usize), DecodeError> {
+                    let mut decoder = Decoder::new(buffer, endian);
+                    let bytes = decoder.read_bytes()?;
+                    let value = match endian {
+                        Endian::Little => Self::from_le_bytes(bytes),
+                        Endian::Big => Self::from_be_bytes(bytes),
+                    };
+                    Ok((value, decoder.position()))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_for_numbers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl Encode for bool {
+    fn encode(&self, buffer: &mut [u8], endian: Endian) -> Result<usize, EncodeError> {
+        u8::from(*self).encode(buffer, endian)
+    }
+}
+
+impl Decode for bool {
+    fn decode(buffer: &[u8], endian: Endian) -> Result<(Self, usize), DecodeError> {
+        match u8::decode(buffer, endian)? {
+            (0, read) => Ok((false, read)),
+            (1, read) => Ok((true, read)),
+            _ => Err(DecodeError::new(0, DecodeErrorKind::InvalidBool)),
+        }
+    }
+}
+
+impl Encode for char {
+    fn encode(&self, buffer: &mut [u8], endian: Endian) -> Result<usize, EncodeError> {
+        u32::from(*self).encode(buffer, endian)
+    }
+}
+
+impl Decode for char {
+    fn decode(buffer: &[u8], endian: Endian) -> Result<(Self, usize), DecodeError> {
+        let (code_point, read) = u32::decode(buffer, endian)?;
+        let value = Self::from_u32(code_point).ok_or_else(|| DecodeError::new(0, DecodeErrorKind::InvalidChar))?;
+        Ok((value, read))
+    }
+}
+
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode(&self, buffer: &mut [u8], endian: Endian) -> Result<usize, EncodeError> {
+        let mut encoder = Encoder::new(buffer, endian);
+        for element in self {
+            encoder.encode(element)?;
+        }
+        Ok(encoder.position())
+    }
+}
+
+/// Arrays are filled with [`Default::default`] before each element is read, since there is no
+/// stable way to build an array from fallible reads without allocating.
+impl<T: Decode + Default, const N: usize> Decode for [T; N] {
+    fn decode(buffer: &[u8], endian: Endian) -> Result<(Self, usize), DecodeError> {
+        let mut decoder = Decoder::new(buffer, endian);
+        let mut array = core::array::from_fn(|_| T::default());
+        for element in &mut array {
+            *element = decoder.decode()?;
+        }
+        Ok((array, decoder.position()))
+    }
+}
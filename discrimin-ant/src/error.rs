@@ -0,0 +1,391 @@
+//! Errors returned by the code generated by `discrimin-ant-proc`.
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// The error returned when a string is neither the name nor the discriminant of any variant of a
+/// fieldless enum.
+///
+/// Since this crate does not allocate, only the first [`ParseVariantError::MAX_INPUT_LEN`] bytes of
+/// the rejected input are kept.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseVariantError {
+    /// The start of the rejected input.
+    input: [u8; Self::MAX_INPUT_LEN],
+    /// The number of bytes of `input` that are used.
+    len: usize,
+    /// Whether the rejected input was longer than [`ParseVariantError::MAX_INPUT_LEN`].
+    truncated: bool,
+    /// The name of the enum that was being parsed.
+    enum_name: &'static str,
+}
+
+impl ParseVariantError {
+    /// The maximum number of bytes of the rejected input that are kept.
+    pub const MAX_INPUT_LEN: usize = 64;
+
+    /// Creates an error for input that could not be parsed into the enum `enum_name`.
+    ///
+    /// * `input`: The rejected input.
+    /// * `enum_name`: The name of the enum.
+    pub fn new(input: &str, enum_name: &'static str) -> Self {
+        let mut len = input.len().min(Self::MAX_INPUT_LEN);
+        while !input.is_char_boundary(len) {
+            len = len.saturating_sub(1);
+        }
+        let mut buffer = [0; Self::MAX_INPUT_LEN];
+        buffer[..len].copy_from_slice(&input.as_bytes()[..len]);
+        Self {
+            input: buffer,
+            len,
+            truncated: len < input.len(),
+            enum_name,
+        }
+    }
+
+    /// Returns the rejected input, shortened to at most [`ParseVariantError::MAX_INPUT_LEN`] bytes.
+    pub fn input(&self) -> &str {
+        core::str::from_utf8(&self.input[..self.len]).unwrap_or_default()
+    }
+
+    /// Returns whether [`ParseVariantError::input`] was shortened.
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns the name of the enum that was being parsed.
+    pub const fn enum_name(&self) -> &'static str {
+        self.enum_name
+    }
+}
+
+impl fmt::Debug for ParseVariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseVariantError")
+            .field("input", &self.input())
+            .field("truncated", &self.truncated)
+            .field("enum_name", &self.enum_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for ParseVariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ellipsis = if self.truncated { "..." } else { "" };
+        write!(f, "unknown variant `{}{ellipsis}` for `{}`", self.input(), self.enum_name)
+    }
+}
+
+impl core::error::Error for ParseVariantError {}
+
+/// The error returned when a value is not the discriminant of any variant of the enum `E`.
+///
+/// * `E`: The enum that was being converted to.
+/// * `R`: The discriminant type of the enum.
+pub struct UnknownDiscriminant<E, R> {
+    /// The rejected value.
+    value: R,
+    /// The name of the enum that was being converted to.
+    enum_name: &'static str,
+    /// Ties the error to the enum without owning one.
+    enum_type: PhantomData<fn() -> E>,
+}
+
+impl<E, R> UnknownDiscriminant<E, R> {
+    /// Creates an error for a value that is not a discriminant of the enum `enum_name`.
+    ///
+    /// * `value`: The rejected value.
+    /// * `enum_name`: The name of the enum.
+    pub const fn new(value: R, enum_name: &'static str) -> Self {
+        Self {
+            value,
+            enum_name,
+            enum_type: PhantomData,
+        }
+    }
+
+    /// Returns the rejected value.
+    pub const fn value(&self) -> &R {
+        &self.value
+    }
+
+    /// Returns the name of the enum that was being converted to.
+    pub const fn enum_name(&self) -> &'static str {
+        self.enum_name
+    }
+
+    /// Consumes the error, returning the rejected value.
+    pub fn into_value(self) -> R {
+        self.value
+    }
+}
+
+impl<E, R: Clone> Clone for UnknownDiscriminant<E, R> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone(), self.enum_name)
+    }
+}
+
+impl<E, R: Copy> Copy for UnknownDiscriminant<E, R> {}
+
+impl<E, R: PartialEq> PartialEq for UnknownDiscriminant<E, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.enum_name == other.enum_name
+    }
+}
+
+impl<E, R: Eq> Eq for UnknownDiscriminant<E, R> {}
+
+impl<E, R: Hash> Hash for UnknownDiscriminant<E, R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.enum_name.hash(state);
+    }
+}
+
+impl<E, R: fmt::Debug> fmt::Debug for UnknownDiscriminant<E, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnknownDiscriminant")
+            .field("value", &self.value)
+            .field("enum_name", &self.enum_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E, R: fmt::Display> fmt::Display for UnknownDiscriminant<E, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown discriminant `{}` for `{}`", self.value, self.enum_name)
+    }
+}
+
+impl<E, R: fmt::Debug + fmt::Display> core::error::Error for UnknownDiscriminant<E, R> {}
+
+/// The error returned when a value is not the discriminant of a current variant of the enum `E`,
+/// telling apart values that were retired or reserved from values that were never used.
+///
+/// * `E`: The enum that was being converted to.
+/// * `R`: The discriminant type of the enum.
+pub enum DiscriminantError<E, R> {
+    /// The value is not the discriminant of any variant.
+    Unknown(UnknownDiscriminant<E, R>),
+    /// The value belonged to a variant that has been retired, or is reserved.
+    Retired(UnknownDiscriminant<E, R>),
+}
+
+impl<E, R> DiscriminantError<E, R> {
+    /// Returns the rejected value.
+    pub const fn value(&self) -> &R {
+        match self {
+            Self::Unknown(error) | Self::Retired(error) => error.value(),
+        }
+    }
+
+    /// Returns the name of the enum that was being converted to.
+    pub const fn enum_name(&self) -> &'static str {
+        match self {
+            Self::Unknown(error) | Self::Retired(error) => error.enum_name(),
+        }
+    }
+
+    /// Returns whether the rejected value was retired or reserved.
+    pub const fn is_retired(&self) -> bool {
+        matches!(self, Self::Retired(_))
+    }
+}
+
+impl<E, R: Clone> Clone for DiscriminantError<E, R> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Unknown(error) => Self::Unknown(error.clone()),
+            Self::Retired(error) => Self::Retired(error.clone()),
+        }
+    }
+}
+
+impl<E, R: Copy> Copy for DiscriminantError<E, R> {}
+
+impl<E, R: PartialEq> PartialEq for DiscriminantError<E, R> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Unknown(error), Self::Unknown(other)) | (Self::Retired(error), Self::Retired(other)) => error == other,
+            _ => false,
+        }
+    }
+}
+
+impl<E, R: Eq> Eq for DiscriminantError<E, R> {}
+
+impl<E, R: Hash> Hash for DiscriminantError<E, R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_retired().hash(state);
+        match self {
+            Self::Unknown(error) | Self::Retired(error) => error.hash(state),
+        }
+    }
+}
+
+impl<E, R: fmt::Debug> fmt::Debug for DiscriminantError<E, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(error) => f.debug_tuple("Unknown").field(error).finish(),
+            Self::Retired(error) => f.debug_tuple("Retired").field(error).finish(),
+        }
+    }
+}
+
+impl<E, R: fmt::Display> fmt::Display for DiscriminantError<E, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(error) => error.fmt(f),
+            Self::Retired(error) => write!(f, "retired discriminant `{}` for `{}`", error.value(), error.enum_name()),
+        }
+    }
+}
+
+impl<E, R: fmt::Debug + fmt::Display> core::error::Error for DiscriminantError<E, R> {}
+
+/// The error returned when a value does not fit in the buffer it is being encoded to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EncodeError {
+    /// The offset in the buffer of the first byte that did not fit.
+    offset: usize,
+}
+
+impl EncodeError {
+    /// Creates an error for a buffer that ran out at `offset`.
+    ///
+    /// * `offset`: The offset in the buffer of the first byte that did not fit.
+    pub const fn new(offset: usize) -> Self {
+        Self { offset }
+    }
+
+    /// Returns the offset in the buffer of the first byte that did not fit.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Moves the error from a value encoded at `offset` in a larger buffer to that buffer.
+    ///
+    /// * `offset`: The offset of the value in the larger buffer.
+    #[must_use]
+    pub const fn nested_at(self, offset: usize) -> Self {
+        Self::new(self.offset.saturating_add(offset))
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer too small at byte {}", self.offset)
+    }
+}
+
+impl core::error::Error for EncodeError {}
+
+/// A discriminant read from a buffer, widened so that the value of any repr fits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodedDiscriminant {
+    /// The discriminant of an enum with a signed repr, e.g. `i16`.
+    Signed(i128),
+    /// The discriminant of an enum with an unsigned repr, e.g. `u8`.
+    Unsigned(u128),
+}
+
+/// Implements `From` for `DecodedDiscriminant` for each repr that can be decoded.
+macro_rules! impl_decoded_discriminant_from {
+    ($variant:ident: $($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for DecodedDiscriminant {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_decoded_discriminant_from!(Signed: i8, i16, i32, i64, i128);
+impl_decoded_discriminant_from!(Unsigned: u8, u16, u32, u64, u128);
+
+impl fmt::Display for DecodedDiscriminant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(value) => write!(f, "{value}"),
+            Self::Unsigned(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Why a value could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeErrorKind {
+    /// The buffer ended before the value did.
+    UnexpectedEnd,
+    /// The discriminant `value` is not that of any current variant of the enum `enum_name`.
+    UnknownDiscriminant {
+        /// The name of the enum that was being decoded.
+        enum_name: &'static str,
+        /// The discriminant that was read.
+        value: DecodedDiscriminant,
+    },
+    /// A `bool` was neither `0` nor `1`.
+    InvalidBool,
+    /// A `char` was not a Unicode scalar value.
+    InvalidChar,
+}
+
+/// The error returned when a value cannot be decoded from a buffer, along with where in the buffer
+/// it went wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DecodeError {
+    /// The offset in the buffer of the value that could not be decoded.
+    offset: usize,
+    /// Why the value could not be decoded.
+    kind: DecodeErrorKind,
+}
+
+impl DecodeError {
+    /// Creates an error for a value at `offset` that could not be decoded.
+    ///
+    /// * `offset`: The offset in the buffer of the value.
+    /// * `kind`: Why the value could not be decoded.
+    pub const fn new(offset: usize, kind: DecodeErrorKind) -> Self {
+        Self { offset, kind }
+    }
+
+    /// Returns the offset in the buffer of the value that could not be decoded.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns why the value could not be decoded.
+    pub const fn kind(&self) -> DecodeErrorKind {
+        self.kind
+    }
+
+    /// Moves the error from a value decoded at `offset` in a larger buffer to that buffer.
+    ///
+    /// * `offset`: The offset of the value in the larger buffer.
+    #[must_use]
+    pub const fn nested_at(self, offset: usize) -> Self {
+        Self::new(self.offset.saturating_add(offset), self.kind)
+    }
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => f.write_str("unexpected end of buffer"),
+            Self::UnknownDiscriminant { enum_name, value } => write!(f, "unknown discriminant `{value}` for `{enum_name}`"),
+            Self::InvalidBool => f.write_str("invalid bool"),
+            Self::InvalidChar => f.write_str("invalid char"),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)
+    }
+}
+
+impl core::error::Error for DecodeError {}
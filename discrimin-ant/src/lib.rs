@@ -22,6 +22,15 @@
 //! - A fieldless version of the enum (regardless of whether or not the enum is already fieldless)
 //! - An `impl` for `Discriminantable` on the fieldless version of the enum
 //! - Other utilities on the fieldless version to ease use
+//! - Optionally, `serde::Serialize` and `serde::Deserialize` implementations that write the
+//!   discriminant of each variant instead of its index (requires the `serde` feature)
+//! - Optionally, `schemars::JsonSchema` implementations describing that encoding (requires the
+//!   `schemars` feature)
+//! - Optionally, [`Encode`] and [`Decode`] implementations that write the discriminant followed by
+//!   each field to a byte buffer, without `serde` or an allocator
+//!
+//! The same code can also be generated with `#[derive(Discriminant)]` for enums that already have
+//! a primitive `repr`, such as `#[repr(u8)]`.
 //!
 //! Specifically, given the enum
 //! ```no_run
@@ -44,7 +53,12 @@
 //!     Five { x: u32 } = 5,
 //!     Six { x: u32 },
 //! }
+//! // Recompiles the enum when `DISCRIMIN_ANT_EXPLICIT` changes, see the `explicit` option.
+//! const _: Option<&str> = option_env!("DISCRIMIN_ANT_EXPLICIT");
 //! impl ComplexUEnum {
+//!     #[doc = "A hash of the name, repr, variant names and discriminants of [ComplexUEnum], for checking that peers agree on them."]
+//!     pub const SCHEMA_HASH: u64 = 0xac5e_b081_0175_52e3;
+//!
 //!     #[doc = r" Returns the discriminant of [Self]."]
 //!     pub const fn discriminant(&self) -> u8 {
 //!         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
@@ -52,12 +66,14 @@
 //! }
 //! impl discrimin_ant::Discriminantable for ComplexUEnum {
 //!     type Discriminant = u8;
+//!     const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
 //!
 //!     fn discriminant(&self) -> Self::Discriminant {
 //!         self.discriminant()
 //!     }
 //! }
 //! #[doc = "Fieldless representations of [ComplexUEnum]. Used to extract discriminants without fully constructing the enum."]
+//! #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 //! #[repr(u8)]
 //! pub enum ComplexUEnum_ {
 //!     #[doc = "A fieldless version of [ComplexUEnum::One], used to extract the variant's discriminant without needing to fully construct it."]
@@ -70,20 +86,58 @@
 //!     Six = 5 + 1u8,
 //! }
 //! impl ComplexUEnum_ {
+//!     #[doc = r" Every variant of [Self], in declaration order."]
+//!     pub const VARIANTS: [Self; 4] = [Self::One, Self::Two, Self::Five, Self::Six];
+//!     #[doc = r" The number of variants of [Self]."]
+//!     pub const COUNT: usize = 4;
+//!     #[doc = r" The discriminant of every variant of [Self], in declaration order."]
+//!     pub const DISCRIMINANTS: [u8; 4] = [Self::One as u8, Self::Two as u8, Self::Five as u8, Self::Six as u8];
+//!
 //!     #[doc = r" Returns the discriminant of [Self]."]
 //!     pub const fn discriminant(&self) -> u8 {
 //!         unsafe { *core::ptr::from_ref::<Self>(self).cast::<u8>() }
 //!     }
+//!
+//!     #[doc = r" Returns an iterator over every variant of [Self], in declaration order."]
+//!     pub fn iter() -> core::array::IntoIter<Self, 4> {
+//!         Self::VARIANTS.into_iter()
+//!     }
+//!
+//!     #[doc = r" Returns the name of the variant."]
+//!     pub const fn name(&self) -> &'static str {
+//!         match *self {
+//!             Self::One => "One",
+//!             Self::Two => "Two",
+//!             Self::Five => "Five",
+//!             Self::Six => "Six",
+//!         }
+//!     }
 //! }
-//! impl discrimin_ant::Discriminantable for ComplexUEnum_ {
-//!     type Discriminant = u8;
+//! impl core::fmt::Display for ComplexUEnum_ {
+//!     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//!         f.write_str(self.name())
+//!     }
+//! }
+//! impl core::str::FromStr for ComplexUEnum_ {
+//!     type Err = discrimin_ant::ParseVariantError;
 //!
-//!     fn discriminant(&self) -> Self::Discriminant {
-//!         self.discriminant()
+//!     fn from_str(s: &str) -> Result<Self, Self::Err> {
+//!         match s {
+//!             "One" => Ok(Self::One),
+//!             "Two" => Ok(Self::Two),
+//!             "Five" => Ok(Self::Five),
+//!             "Six" => Ok(Self::Six),
+//!             _ => s
+//!                 .parse::<u8>()
+//!                 .ok()
+//!                 .and_then(|discriminant| Self::try_from(discriminant).ok())
+//!                 .ok_or_else(|| discrimin_ant::ParseVariantError::new(s, stringify!(ComplexUEnum_))),
+//!         }
 //!     }
 //! }
 //! impl TryFrom<u8> for ComplexUEnum_ {
-//!     type Error = ();
+//!     type Error = discrimin_ant::UnknownDiscriminant<Self, u8>;
+//!
 //!     fn try_from(value: u8) -> Result<Self, Self::Error> {
 //!         if value == (1) {
 //!             return Ok(Self::One);
@@ -97,7 +151,7 @@
 //!         if value == (5 + 1u8) {
 //!             return Ok(Self::Six);
 //!         }
-//!         Err(())
+//!         Err(discrimin_ant::UnknownDiscriminant::new(value, stringify!(ComplexUEnum_)))
 //!     }
 //! }
 //! impl From<&ComplexUEnum> for ComplexUEnum_ {
@@ -110,6 +164,18 @@
 //!         }
 //!     }
 //! }
+//! impl ComplexUEnum_ {
+//!     #[doc = "A hash of the name, repr, variant names and discriminants of [ComplexUEnum], for checking that peers agree on them."]
+//!     pub const SCHEMA_HASH: u64 = 0xac5e_b081_0175_52e3;
+//! }
+//! impl discrimin_ant::Discriminantable for ComplexUEnum_ {
+//!     type Discriminant = u8;
+//!     const SCHEMA_HASH: u64 = Self::SCHEMA_HASH;
+//!
+//!     fn discriminant(&self) -> Self::Discriminant {
+//!         self.discriminant()
+//!     }
+//! }
 //! ```
 //!
 //! Note that the `discriminant` attribute only supports [primitive representations](https://doc.rust-lang.org/reference/type-layout.html#primitive-representations)
@@ -117,11 +183,45 @@
 //! Nevertheless, manual implementations of `Discriminant` can be made for any enum (and technically and other object).
 #![no_std]
 
+#[cfg(feature = "schemars")]
+extern crate alloc;
+
+mod codec;
+mod error;
+mod schema;
+
+pub use codec::{Decode, Decoder, Encode, Encoder, Endian};
+pub use error::{DecodeError, DecodeErrorKind, DecodedDiscriminant, DiscriminantError, EncodeError, ParseVariantError, UnknownDiscriminant};
+pub use schema::combine_schema_hashes;
+
 /// An enum with an accessible discriminant.
 pub trait Discriminantable {
     /// The type of the discriminant.
     type Discriminant: num_traits::PrimInt;
 
+    /// A hash of the enum's name, repr, variants and discriminants, which peers can exchange to
+    /// check that they agree on the enum. It is the same on every platform and compiler version.
+    /// Several of these can be combined with [`combine_schema_hashes`].
+    ///
+    /// Generated by the `discriminant` attribute. Manual implementations default to `0`.
+    const SCHEMA_HASH: u64 = 0;
+
     /// Returns the discriminant of the enum.
     fn discriminant(&self) -> Self::Discriminant;
 }
+
+/// Re-export of [Serde](https://crates.io/crates/serde) for use by the code generated with the
+/// `serde` option of the `discriminant` attribute.
+#[cfg(feature = "serde")]
+pub use serde;
+
+/// Re-export of [Schemars](https://crates.io/crates/schemars) for use by the code generated with
+/// the `schemars` option of the `discriminant` attribute.
+#[cfg(feature = "schemars")]
+pub use schemars;
+
+/// Re-export of `Cow`, which `schemars::JsonSchema` uses for schema names, for use by the code
+/// generated with the `schemars` option of the `discriminant` attribute.
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+pub use alloc::borrow::Cow;
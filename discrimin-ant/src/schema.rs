@@ -0,0 +1,38 @@
+//! Helpers for working with [`Discriminantable::SCHEMA_HASH`](crate::Discriminantable::SCHEMA_HASH).
+
+/// The FNV-1a offset basis for 64-bit hashes.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The FNV-1a prime for 64-bit hashes.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Combines the schema hashes of several enums into a single fingerprint, such as one for a whole
+/// protocol. The order of the hashes matters.
+///
+/// The result is the 64-bit FNV-1a hash of the little-endian bytes of each hash, so it is the same
+/// on every platform.
+///
+/// ```rust
+/// use discrimin_ant::{Discriminantable, combine_schema_hashes};
+///
+/// const fn fingerprint<A: Discriminantable, B: Discriminantable>() -> u64 {
+///     combine_schema_hashes(&[A::SCHEMA_HASH, B::SCHEMA_HASH])
+/// }
+///
+/// assert_ne!(combine_schema_hashes(&[1, 2]), combine_schema_hashes(&[2, 1]));
+/// ```
+///
+/// * `hashes`: The schema hashes to combine.
+pub const fn combine_schema_hashes(hashes: &[u64]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut hashes = hashes;
+    while let [next, rest @ ..] = hashes {
+        let bytes = next.to_le_bytes();
+        let mut bytes = bytes.as_slice();
+        while let [byte, rest @ ..] = bytes {
+            hash = (hash ^ *byte as u64).wrapping_mul(FNV_PRIME);
+            bytes = rest;
+        }
+        hashes = rest;
+    }
+    hash
+}